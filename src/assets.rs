@@ -1,7 +1,13 @@
 //! Module for instantion of assets and mixins.
 
-use std::borrow::Cow;
-use crate::{Attribute, Component, ComponentVec, simple_enum};
+use std::{borrow::Cow, time::Duration};
+use crate::{Attribute, Component, ComponentVec, simple_enum, utils::{ConstDefault, owned_attrs, owned_str}};
+#[cfg(feature = "web")]
+use js_sys::{Promise, Reflect};
+#[cfg(feature = "web")]
+use wasm_bindgen::JsValue;
+#[cfg(feature = "web")]
+use wasm_bindgen_futures::JsFuture;
 
 /// Constructs an Assets object for use in a scene.
 /// Example:
@@ -14,20 +20,38 @@ use crate::{Attribute, Component, ComponentVec, simple_enum};
 ///     mixin!
 ///     {
 ///         "intersect_ray", 
-///         ("raycaster", component!
-///         {
-///             RayCaster,
-///             objects: List(Cow::Borrowed(&[Cow::Borrowed("#ramen-cube, #water")]))
-///         })
+///         ("raycaster", RayCaster::targeting([Selector::id("ramen-cube"), Selector::id("water")]))
 ///     }
 /// },
 /// ```
+/// An optional `timeout` (in milliseconds or as a `Duration`, omitted when 0) 
+/// and a bracketed list of additional `<a-assets>` attributes may precede the 
+/// assets:
+/// ```ignore
+/// assets!
+/// {
+///     timeout: Duration::from_secs(5), 
+///     attributes: [("crossorigin", "anonymous")],
+///     Image::new("ramen", "/pics/ramen.png")
+/// },
+/// ```
 #[macro_export]
 macro_rules! assets
 {
+    (timeout: $timeout:expr, attributes: [$(($attr_id:literal, $attr_value:expr)),* $(,)?], $($asset:expr),*) => 
+    {
+        Assets::new($crate::assets::IntoTimeoutMs::into_timeout_ms($timeout), vec!($($asset),*)).with_attributes($crate::attributes_vec!
+        {
+            $(($attr_id, $attr_value)),*
+        })
+    };
+    (attributes: [$(($attr_id:literal, $attr_value:expr)),* $(,)?], $($asset:expr),*) => 
+    {
+        assets!(timeout: 0, attributes: [$(($attr_id, $attr_value)),*], $($asset),*)
+    };
     (timeout: $timeout:expr, $($asset:expr),*) => 
     {
-        Assets::new($timeout, vec!($($asset),*))
+        Assets::new($crate::assets::IntoTimeoutMs::into_timeout_ms($timeout), vec!($($asset),*))
     };
     ($($asset:expr),*) => 
     {
@@ -40,7 +64,7 @@ macro_rules! assets
 #[macro_export]
 macro_rules! mixin
 {
-    ($id:expr, $(($cmp_id:literal, $cmp_value:expr)),*) => 
+    ($id:expr, $(($cmp_id:expr, $cmp_value:expr)),*) => 
     {
         Mixin::new($id, $crate::components_vec!
         {
@@ -54,13 +78,130 @@ macro_rules! mixin
 pub struct Assets
 {
     pub(crate) timeout_ms: u32,
+    pub(crate) attributes: Vec<Attribute>,
     pub(crate) assets: Vec<Asset>
 }
 impl Assets
 {
     pub fn new(timeout_ms: u32, assets: Vec<Asset>) -> Self
     {
-        Self { timeout_ms, assets }
+        Self { timeout_ms, attributes: vec!(), assets }
+    }
+
+    /// Like [`Assets::new`], but with the timeout given as a `Duration`. 
+    /// Timeouts longer than `u32::MAX` milliseconds are clamped.
+    pub fn with_timeout(timeout: Duration, assets: Vec<Asset>) -> Self
+    {
+        Self::new(timeout.into_timeout_ms(), assets)
+    }
+
+    /// Adds attributes to the `<a-assets>` element, emitted after `timeout`
+    pub fn with_attributes(mut self, attributes: Vec<Attribute>) -> Self
+    {
+        self.attributes.extend(attributes);
+        self
+    }
+
+    /// Additional attributes of the `<a-assets>` element
+    pub fn attributes(&self) -> &Vec<Attribute>
+    {
+        &self.attributes
+    }
+
+    /// Mutable access to the additional attributes of the `<a-assets>` element
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute>
+    {
+        &mut self.attributes
+    }
+
+    /// Converts every borrowed string held by these assets (ids, sources, 
+    /// attributes and mixin component names) into an owned string
+    pub fn into_owned(self) -> Self
+    {
+        Self
+        {
+            timeout_ms: self.timeout_ms,
+            attributes: owned_attrs(self.attributes),
+            assets: self.assets.into_iter().map(Asset::into_owned).collect()
+        }
+    }
+
+    /// Find an asset or mixin by its id
+    pub fn get(&self, id: &str) -> Option<&Asset>
+    {
+        self.assets.iter().find(|asset| asset.id() == id)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Asset>
+    {
+        self.assets.iter()
+    }
+
+    /// Waits until the given `<a-assets>` element fires its `loaded` event, 
+    /// once every asset has loaded. Resolves immediately if the assets have 
+    /// already loaded. Aframe gives up waiting after the `timeout` attribute 
+    /// elapses and fires `timeout` before `loaded`, in which case this returns
+    /// an error.
+    #[cfg(feature = "web")]
+    pub async fn wait_loaded(assets_el: &web_sys::Element) -> Result<(), JsValue>
+    {
+        if Reflect::get(assets_el, &JsValue::from_str("hasLoaded"))?.is_truthy()
+        {
+            return Ok(());
+        }
+        let mut listen_result = Ok(());
+        let loaded = Promise::new(&mut |resolve, reject|
+        {
+            let options = web_sys::AddEventListenerOptions::new();
+            options.set_once(true);
+            listen_result = assets_el
+                .add_event_listener_with_callback_and_add_event_listener_options("timeout", &reject, &options)
+                .and_then(|_| assets_el.add_event_listener_with_callback_and_add_event_listener_options("loaded", &resolve, &options));
+        });
+        listen_result?;
+        JsFuture::from(loaded).await
+            .map(|_| ())
+            .map_err(|_| JsValue::from_str("Timed out waiting for assets to load"))
+    }
+}
+impl IntoIterator for Assets
+{
+    type Item = Asset;
+    type IntoIter = std::vec::IntoIter<Asset>;
+    fn into_iter(self) -> Self::IntoIter 
+    {
+        self.assets.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a Assets
+{
+    type Item = &'a Asset;
+    type IntoIter = std::slice::Iter<'a, Asset>;
+    fn into_iter(self) -> Self::IntoIter 
+    {
+        self.assets.iter()
+    }
+}
+
+/// Conversion of an `assets!` timeout, given either as milliseconds or as a 
+/// `Duration`, into milliseconds.
+#[doc(hidden)]
+pub trait IntoTimeoutMs
+{
+    fn into_timeout_ms(self) -> u32;
+}
+impl IntoTimeoutMs for u32
+{
+    fn into_timeout_ms(self) -> u32
+    {
+        self
+    }
+}
+impl IntoTimeoutMs for Duration
+{
+    fn into_timeout_ms(self) -> u32
+    {
+        u32::try_from(self.as_millis()).unwrap_or(u32::MAX)
     }
 }
 
@@ -76,6 +217,18 @@ pub enum Asset
 }
 impl Asset
 {
+    pub fn id(&self) -> &Cow<'static, str>
+    {
+        match self
+        {
+            Asset::Item(item) => &item.id,
+            Asset::Image(image) => &image.id,
+            Asset::Video(video) => &video.id,
+            Asset::Audio(audio) => &audio.id,
+            Asset::Mixin(mixin) => &mixin.id,
+        }
+    }
+
     pub fn src(&self) -> &Cow<'static, str>
     {
         match self
@@ -87,6 +240,20 @@ impl Asset
             Asset::Mixin(_) => &Cow::Borrowed(""),
         }
     }
+
+    /// Converts the id, source and any other borrowed strings of this asset 
+    /// into owned strings
+    pub fn into_owned(self) -> Self
+    {
+        match self
+        {
+            Asset::Item(item) => Asset::Item(AssetItem { id: owned_str(item.id), src: owned_str(item.src), ..item }),
+            Asset::Image(image) => Asset::Image(Image { id: owned_str(image.id), src: owned_str(image.src) }),
+            Asset::Video(video) => Asset::Video(Video { id: owned_str(video.id), src: owned_str(video.src), ..video }),
+            Asset::Audio(audio) => Asset::Audio(Audio { id: owned_str(audio.id), src: owned_str(audio.src), ..audio }),
+            Asset::Mixin(mixin) => Asset::Mixin(Mixin { id: owned_str(mixin.id), components: mixin.components.into_owned() })
+        }
+    }
 }
 impl From<&Asset> for Vec<Attribute>
 {
@@ -108,20 +275,54 @@ impl From<&Asset> for Vec<Attribute>
 pub struct AssetItem
 {
     pub(crate) id: Cow<'static, str>,
-    pub(crate) src: Cow<'static, str>
+    pub(crate) src: Cow<'static, str>,
+    pub(crate) response_type: Option<ResponseType>
 }
 impl From<&AssetItem> for Vec<Attribute>
 {
     fn from(item: &AssetItem) -> Self 
     {
-        vec!(Attribute::new("id", item.id.clone()), Attribute::new("src", item.src.clone()))
+        let mut vec = vec!(Attribute::new("id", item.id.clone()), Attribute::new("src", item.src.clone()));
+        if let Some(response_type) = item.response_type
+        {
+            vec.push(Attribute::new("response-type", response_type.to_string()));
+        }
+        vec
     }
 }
 impl AssetItem
 {
     pub fn new(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>) -> Asset
     {
-        Asset::Item(Self { id: id.into(), src: src.into() })
+        Asset::Item(Self { id: id.into(), src: src.into(), response_type: None })
+    }
+    pub fn with_response_type(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>, response_type: ResponseType) -> Asset
+    {
+        Asset::Item(Self { id: id.into(), src: src.into(), response_type: Some(response_type) })
+    }
+}
+
+/// Constructors for preloaded 3D model assets. Preloading a model and 
+/// referencing it by id means it is fetched while the scene loads instead of 
+/// the first time it is displayed:
+/// ```ignore
+/// let assets = assets!
+/// {
+///     Model::gltf("robot", "/models/robot.glb")
+/// };
+/// let robot = entity!
+/// {
+///     components: ("gltf-model", component!(GltfModel, src: Selector::id("robot")))
+/// };
+/// ```
+pub struct Model;
+impl Model
+{
+    /// A glTF (`.gltf` or `.glb`) model, fetched as an `arraybuffer` so that 
+    /// binary models are cached intact for the `gltf-model` component.
+    pub fn gltf(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>) -> Asset
+    {
+        AssetItem::with_response_type(id, src, ResponseType::ArrayBuffer)
     }
 }
 
@@ -155,34 +356,71 @@ pub struct Video
     pub(crate) id: Cow<'static, str>,
     pub(crate) src: Cow<'static, str>,
     pub(crate) autoplay: bool,
-    pub(crate) preload: Preload
+    pub(crate) preload: Preload,
+    pub(crate) muted: bool,
+    pub(crate) looping: bool,
+    pub(crate) plays_inline: bool
 }
 impl From<&Video> for Vec<Attribute>
 {
     fn from(video: &Video) -> Self 
     {
-        vec!
-        (
-            Attribute::new("id", video.id.clone()), 
-            Attribute::new("src", video.src.clone()), 
-            Attribute::new("autoplay", video.autoplay.to_string()), 
-            Attribute::new("preload", video.preload.to_string())
-        )
+        let mut attrs = media_attributes(video.id.clone(), video.src.clone(), video.autoplay, video.preload);
+        let flags = [("muted", video.muted), ("loop", video.looping), ("playsinline", video.plays_inline)];
+        attrs.extend(flags.into_iter().filter(|(_, set)| *set).map(|(name, _)| Attribute::new(name, "true")));
+        attrs
     }
 }
 impl Video
 {
     pub fn new(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>) -> Asset
     {
-        Asset::Video(Self { id: id.into(), src: src.into(), autoplay: false, preload: Preload::None })
+        Self::with_options(id, src, VideoOptions::DEFAULT)
     }
     pub fn autoplay(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>) -> Asset
     {
-        Asset::Video(Self { id: id.into(), src: src.into(), autoplay: true, preload: Preload::None })
+        Self::with_options(id, src, VideoOptions { autoplay: true, ..VideoOptions::DEFAULT })
     }
     pub fn preload(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>, preload: Preload) -> Asset
     {
-        Asset::Video(Self { id: id.into(), src: src.into(), autoplay: false, preload })
+        Self::with_options(id, src, VideoOptions { preload, ..VideoOptions::DEFAULT })
+    }
+
+    /// A video which plays muted and inline as soon as it loads. Browsers 
+    /// block autoplay of videos with sound, so this is usually what's wanted 
+    /// for an autoplaying 360 video.
+    pub fn autoplay_muted(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>) -> Asset
+    {
+        Self::with_options(id, src, VideoOptions { autoplay: true, muted: true, plays_inline: true, ..VideoOptions::DEFAULT })
+    }
+
+    pub fn with_options(id: impl Into<Cow<'static, str>>, src: impl Into<Cow<'static, str>>, options: VideoOptions) -> Asset
+    {
+        let VideoOptions { autoplay, preload, muted, looping, plays_inline } = options;
+        Asset::Video(Self { id: id.into(), src: src.into(), autoplay, preload, muted, looping, plays_inline })
+    }
+}
+
+/// Playback options for a [`Video`]. Each flag is only written to the 
+/// `<video>` element when set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoOptions
+{
+    pub autoplay: bool,
+    pub preload: Preload,
+    pub muted: bool,
+    pub looping: bool,
+    pub plays_inline: bool
+}
+impl ConstDefault for VideoOptions
+{
+    const DEFAULT: Self = Self { autoplay: false, preload: Preload::None, muted: false, looping: false, plays_inline: false };
+}
+impl Default for VideoOptions
+{
+    fn default() -> Self
+    {
+        Self::DEFAULT
     }
 }
 
@@ -199,22 +437,24 @@ impl From<&Audio> for Vec<Attribute>
 {
     fn from(audio: &Audio) -> Self 
     {
-        let mut vec = vec!
-        (
-            Attribute::new("id", audio.id.clone()), 
-            Attribute::new("src", audio.src.clone()), 
-        );
-        if audio.autoplay
-        {
-            vec.push(Attribute::new("autoplay", "true"));
-        }
-        match audio.preload
-        {
-            Preload::None => (),
-            preload => vec.push(Attribute::new("preload", preload.to_string()))
-        }
-        vec
+        media_attributes(audio.id.clone(), audio.src.clone(), audio.autoplay, audio.preload)
+    }
+}
+/// Attributes shared by video and audio assets. `autoplay` and `preload` are
+/// left out when they are `false` and `Preload::None`, so that identical media
+/// produce identical elements.
+fn media_attributes(id: Cow<'static, str>, src: Cow<'static, str>, autoplay: bool, preload: Preload) -> Vec<Attribute>
+{
+    let mut attrs = vec!(Attribute::new("id", id), Attribute::new("src", src));
+    if autoplay
+    {
+        attrs.push(Attribute::new("autoplay", "true"));
+    }
+    if preload != Preload::None
+    {
+        attrs.push(Attribute::new("preload", preload.to_string()));
     }
+    attrs
 }
 impl Audio
 {
@@ -263,4 +503,13 @@ simple_enum!
     Auto => "auto", 
     Metadata => "metadata", 
     None => "none"
+);
+
+simple_enum!
+(
+    /// [a-asset-item](https://aframe.io/docs/1.6.0/core/asset-management-system.html#a-asset-item)
+    /// response type. Aframe fetches the asset as text if this is not set.
+    ResponseType, 
+    ArrayBuffer => "arraybuffer", 
+    Text => "text"
 );
\ No newline at end of file
@@ -0,0 +1,39 @@
+//! Component structs for popular community components which are not part of 
+//! Aframe itself. These are only available with the `community-components` 
+//! feature. The javascript for each of these components must be included in 
+//! the page separately, as with any other component that isn't built-in.
+
+use std::borrow::Cow;
+use crate::utils::*;
+use crate::component::Component;
+use crate::component_struct;
+use crate::complex_enum;
+
+component_struct!
+(
+    /// [look-at](https://github.com/supermedium/superframe/tree/master/components/look-at)
+    /// 
+    /// Requires the `aframe-look-at-component` script to be included in the page.
+    LookAt as "look-at" :alt "{}", target: "target" LookAtTarget = LookAtTarget::Selector { selector: Selector::Raw(Cow::Borrowed("")) }
+);
+complex_enum!
+(
+    /// What a `look-at` component points toward: either an element or a position
+    LookAtTarget,
+    Selector "{}" => { selector: Selector },
+    Position "{}" => { position: Vector3 }
+);
+component_struct!
+(
+    /// [aabb-collider](https://github.com/supermedium/superframe/tree/master/components/aabb-collider)
+    /// 
+    /// Requires the `aframe-aabb-collider-component` script to be included in the
+    /// page. Emits `hitstart` and `hitend` on entities whose axis-aligned 
+    /// bounding boxes start or stop intersecting those matching `objects`.
+    AabbCollider as "aabb-collider",
+    objects: "objects" Selector = Selector::Raw(Cow::Borrowed("")),
+    interval: "interval" u32 = 80,
+    collide_non_visible: "collideNonVisible" bool = false,
+    enabled: "enabled" bool = true,
+    debug: "debug" bool = false
+);
@@ -1,15 +1,17 @@
 use std::
 {
     borrow::Cow, 
+    cell::OnceCell,
     collections::HashMap, 
     fmt::{self, Display, Formatter}, 
     ops::{Deref, DerefMut}
 };
+use htmlify::Attribute;
 
 pub trait Component: Display + std::fmt::Debug + std::any::Any
 {
     fn clone(&self) -> Box<dyn Component>;
-    fn eq(&self, other: &'static dyn Component) -> bool;
+    fn eq(&self, other: &dyn Component) -> bool;
     fn as_map(&self) -> HashMap<Cow<'static, str>, Cow<'static, str>>;
 }
 
@@ -35,6 +37,16 @@ impl Clone for Box<dyn Component>
 #[repr(transparent)]
 pub struct ComponentVec(pub Vec<(Cow<'static, str>, Box<dyn Component>)>);
 
+impl ComponentVec
+{
+    /// Converts every component name into an owned string. Component values 
+    /// are left as they are.
+    pub fn into_owned(self) -> Self
+    {
+        Self(self.0.into_iter().map(|(name, cmp)| (crate::utils::owned_str(name), cmp)).collect())
+    }
+}
+
 impl Deref for ComponentVec
 {
     type Target = Vec<(Cow<'static, str>, Box<dyn Component>)>;
@@ -57,9 +69,9 @@ impl PartialEq for ComponentVec
     fn eq(&self, other: &Self) -> bool 
     {
         self.0.len() == other.0.len() &&
-        matches!(self.0.iter().enumerate().filter(|(i, (_name, cmp))| 
-            cmp.eq(&*unsafe{std::mem::transmute::<&ComponentVec, &'static ComponentVec>(other)}
-                .0[*i].1)).next(), None)
+        self.0.iter()
+            .zip(other.0.iter())
+            .all(|((_, cmp), (_, other_cmp))| Component::eq(&**cmp, &**other_cmp))
     }
 }
 
@@ -71,6 +83,62 @@ impl Clone for ComponentVec
     }
 }
 
+/// A reusable set of baseline components, applied to entities with 
+/// [`Entity::apply_preset`](crate::Entity::apply_preset). Unlike a mixin, a 
+/// preset is resolved when the entity is built, so the components end up 
+/// directly on each entity.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ComponentPreset(ComponentVec);
+
+impl ComponentPreset
+{
+    pub fn new(components: Vec<(Cow<'static, str>, Box<dyn Component>)>) -> Self
+    {
+        Self(ComponentVec(components))
+    }
+
+    /// Adds a component to this preset
+    pub fn with(mut self, name: impl Into<Cow<'static, str>>, cmp: impl Component + 'static) -> Self
+    {
+        self.0.push((name.into(), Box::new(cmp)));
+        self
+    }
+
+    pub fn components(&self) -> &Vec<(Cow<'static, str>, Box<dyn Component>)>
+    {
+        &self.0
+    }
+}
+
+/// Lazily-computed attributes for a `ComponentVec`, so that components are only
+/// formatted once between mutations. Always compares as equal so that it can 
+/// live alongside the components without affecting equality.
+#[derive(Default, Debug, Clone)]
+pub(crate) struct ComponentAttrCache(OnceCell<Vec<Attribute>>);
+
+impl ComponentAttrCache
+{
+    /// Get the cached attributes, formatting the components if necessary
+    pub(crate) fn get(&self, components: &ComponentVec) -> &Vec<Attribute>
+    {
+        self.0.get_or_init(|| components.iter().map(crate::component::cmp_to_attr).collect())
+    }
+
+    /// Clear the cache. Must be called whenever the components may change.
+    pub(crate) fn invalidate(&mut self)
+    {
+        self.0.take();
+    }
+}
+
+impl PartialEq for ComponentAttrCache
+{
+    fn eq(&self, _other: &Self) -> bool 
+    {
+        true
+    }
+}
+
 /// While `component_def!` creates a component that Aframe can access from its 
 /// own runtime, the `component_struct!` macro creates a Rust struct that mimics
 /// the internal details of that Aframe component. Component structs are already
@@ -106,6 +174,11 @@ impl Clone for ComponentVec
 /// 
 /// // This will display as "1.0 1.5 2.0"
 /// ```
+/// The name a component is registered under may be given after the struct name,
+/// as in `StructName as "struct-name"`. This generates a `NAME` constant, so the
+/// name and type used to attach a component cannot drift apart: 
+/// `(StructName::NAME, component!(StructName))`.
+/// 
 /// When using items defined with this macro or with the `complex_enum!` macro 
 /// as fields, a custom display implementation may be used to flatten out the
 /// nested properties and print correctly as a single semicolon-separated list
@@ -113,11 +186,11 @@ impl Clone for ComponentVec
 #[macro_export]
 macro_rules! component_struct
 {
-    ($(#[$outer:meta])* $name:ident $(, $field:ident: $field_name:literal $ty:ty = $default:expr)*) => 
+    ($(#[$outer:meta])* $name:ident $(as $cmp_name:literal)? $(, $field:ident: $field_name:literal $ty:ty = $default:expr)*) => 
     {
-        component_struct!($(#[$outer])* $name concat!($($field_name, ": {};"),*) $(, $field: $field_name $ty = $default)*);
+        component_struct!($(#[$outer])* $name $(as $cmp_name)? concat!($($field_name, ": {};"),*) $(, $field: $field_name $ty = $default)*);
     };
-    ($(#[$outer:meta])* $name:ident $(:$alt:ident)? $fmt:expr $(, $field:ident: $field_name:literal $ty:ty = $default:expr)*) => 
+    ($(#[$outer:meta])* $name:ident $(as $cmp_name:literal)? $(:$alt:ident)? $fmt:expr $(, $field:ident: $field_name:literal $ty:ty = $default:expr)*) => 
     {
         $(#[$outer])*
         #[derive(Debug, Clone, PartialEq, serde::Serialize)]
@@ -138,11 +211,17 @@ macro_rules! component_struct
                         {
                             if $field_name.len() <= 1
                             {
-                                write!(f, "{};", self.$field)?;
+                                // Nested items may use their own spacing, so 
+                                // rewrite them in the same `name: value;` form
+                                // as the rest of the properties.
+                                for prop in $crate::component::split_properties(&$crate::field_string!(&self.$field))
+                                {
+                                    write!(f, "{};", prop)?;
+                                }
                             }
                             else
                             {
-                                write!(f, concat!($field_name, ": {};"), self.$field)?;
+                                write!(f, concat!($field_name, ": {};"), $crate::field_string!(&self.$field))?;
                             }
                         }
                     )*
@@ -150,10 +229,17 @@ macro_rules! component_struct
                 }
                 else
                 {
-                    write!(f, $fmt, $(self.$field),*)
+                    write!(f, $fmt, $($crate::field_string!(&self.$field)),*)
                 }
             }
         }
+        $(
+            impl $name
+            {
+                /// The name this component is registered under in Aframe
+                pub const NAME: &'static str = $cmp_name;
+            }
+        )?
         impl ConstDefault for $name
         {
             const DEFAULT: Self = Self 
@@ -167,13 +253,11 @@ macro_rules! component_struct
             {
                 Box::new(Clone::clone(self))
             }
-            fn eq(&self, other: &'static dyn Component) -> bool
+            fn eq(&self, other: &dyn Component) -> bool
             {
-                match (&&*other as &dyn std::any::Any).downcast_ref::<&&$name>()
-                {
-                    Some(other) => self == **other,
-                    None => false
-                }
+                (other as &dyn std::any::Any)
+                    .downcast_ref::<$name>()
+                    .is_some_and(|other| self == other)
             }
             fn as_map(&self) -> std::collections::HashMap<Cow<'static, str>, Cow<'static, str>>
             {
@@ -182,10 +266,7 @@ macro_rules! component_struct
                 $( if $field_name.len() < 1
                 {
                     let mut inner_map = std::collections::HashMap::new();
-                    for (k, v) in self.$field
-                        .to_string()
-                        .split(";")
-                        .map(str::trim)
+                    for (k, v) in $crate::component::split_properties(&$crate::field_string!(&self.$field))
                         .filter_map(|s| s.split_once(":"))
                     {
                         inner_map.insert
@@ -198,7 +279,7 @@ macro_rules! component_struct
                 }
                 else
                 {
-                    map.insert($field_name.into(), self.$field.to_string().into());
+                    map.insert($field_name.into(), $crate::field_string!(&self.$field).into());
                 })*
                 map
             }
@@ -206,6 +287,109 @@ macro_rules! component_struct
     }
 }
 
+/// Formats a component field. Floating point fields are formatted with 
+/// [`fmt_float`](crate::utils::fmt_float), `Option<bool>` fields as `true`, 
+/// `false` or `null`, and everything else uses `Display`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! field_string
+{
+    ($field:expr) => 
+    {
+        {
+            #[allow(unused_imports)]
+            use $crate::component::{DisplayFieldFmt, FloatFieldFmt, OptionBoolFieldFmt};
+            (&$crate::component::FieldFmt($field)).field_string()
+        }
+    }
+}
+
+/// Splits a `name: value; ...` property string into its trimmed, non-empty 
+/// properties. Only top-level semicolons separate properties, so values such 
+/// as `url(data:image/png;base64,...)` or quoted strings are kept whole.
+#[doc(hidden)]
+pub fn split_properties(props: &str) -> impl Iterator<Item = &str>
+{
+    let mut depth = 0usize;
+    let mut quote = None;
+    props
+        .split(move |c: char|
+        {
+            match (quote, c)
+            {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => (),
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => depth = depth.saturating_sub(1),
+                (None, ';') => return depth == 0,
+                _ => ()
+            }
+            false
+        })
+        .map(str::trim)
+        .filter(|prop| !prop.is_empty())
+}
+
+/// Wrapper used by `field_string!` to pick a formatting method based on the 
+/// type of a field.
+#[doc(hidden)]
+pub struct FieldFmt<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait FloatFieldFmt
+{
+    fn field_string(&self) -> String;
+}
+
+impl FloatFieldFmt for FieldFmt<'_, f32>
+{
+    fn field_string(&self) -> String
+    {
+        crate::utils::fmt_float(*self.0 as f64)
+    }
+}
+
+impl FloatFieldFmt for FieldFmt<'_, f64>
+{
+    fn field_string(&self) -> String
+    {
+        crate::utils::fmt_float(*self.0)
+    }
+}
+
+#[doc(hidden)]
+pub trait OptionBoolFieldFmt
+{
+    fn field_string(&self) -> String;
+}
+
+impl OptionBoolFieldFmt for FieldFmt<'_, Option<bool>>
+{
+    fn field_string(&self) -> String
+    {
+        match self.0
+        {
+            Some(value) => value.to_string(),
+            None => "null".to_owned()
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait DisplayFieldFmt
+{
+    fn field_string(&self) -> String;
+}
+
+impl<T: Display + ?Sized> DisplayFieldFmt for &FieldFmt<'_, T>
+{
+    fn field_string(&self) -> String
+    {
+        self.0.to_string()
+    }
+}
+
 /// A macro to instantiate a component. Mimics struct creation syntax, but allows
 /// any number of fields to be left out (in which case defaults will be used). 
 /// Note that the ability to leave out fields does not extend to struct_like
@@ -214,6 +398,12 @@ macro_rules! component_struct
 /// whereas `component!{component::Camera, active = false}` will create a 
 /// `camera` component with all its fields set to default values except the 
 /// `active` field.
+/// 
+/// The expansion is usable in `const` and `static` items. Each given field is 
+/// swapped into the component's `DEFAULT` in place; the replaced default is 
+/// never dropped, which is sound since a constant can't own any allocations. 
+/// A misspelled or unknown field is reported against that field alone, with a
+/// suggestion for the closest matching field.
 /// ```ignore
 /// // For example:
 /// use aframe::component;
@@ -233,12 +423,78 @@ macro_rules! component
 {
     ($($cmp:ident)::* $(, $field:ident: $val:expr)*) => 
     {
-        $($cmp)::*
         {
-            $($field: $val,)*
-            ..$($cmp)::*::DEFAULT
+            #[allow(unused_mut)]
+            let mut cmp = $($cmp)::*::DEFAULT;
+            $(let _ = std::mem::ManuallyDrop::new(std::mem::replace(&mut cmp.$field, $val));)*
+            cmp
+        }
+    }
+}
+
+/// Creates the name of one instance of a component which allows multiple 
+/// instances per entity (i.e. one registered with `multiple: true`). The name 
+/// and instance id are joined with A-Frame's `__` delimiter at compile time, 
+/// and compilation fails if either part would produce an invalid name. Each 
+/// part must be non-empty, contain only ASCII alphanumerics, `-` and `_`, and 
+/// may not start or end with `_` or contain `__`.
+/// ```ignore
+/// use aframe::component_id;
+/// 
+/// entity!
+/// {
+///     components: 
+///     (component_id!("sound", "ambient"), component!(component::Sound, src: Cow::Borrowed("#ambient"))),
+///     (component_id!("sound", "click"), component!(component::Sound, src: Cow::Borrowed("#click")))
+/// }
+/// // Will contain the components "sound__ambient" and "sound__click"
+/// ```
+#[macro_export]
+macro_rules! component_id
+{
+    ($name:literal, $id:literal) => 
+    {
+        {
+            const _: () = assert!
+            (
+                $crate::component::is_valid_component_id_part($name), 
+                concat!("Invalid component name: \"", $name, "\"")
+            );
+            const _: () = assert!
+            (
+                $crate::component::is_valid_component_id_part($id), 
+                concat!("Invalid component instance id: \"", $id, "\"")
+            );
+            concat!($name, "__", $id)
+        }
+    }
+}
+
+/// Checks that a string may be used on either side of the `__` delimiter in a 
+/// multiple-instance component name. Used by the `component_id!` macro.
+#[doc(hidden)]
+pub const fn is_valid_component_id_part(part: &str) -> bool
+{
+    let bytes = part.as_bytes();
+    if bytes.is_empty() || bytes[0] == b'_' || bytes[bytes.len() - 1] == b'_'
+    {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len()
+    {
+        let byte = bytes[i];
+        if !(byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+        {
+            return false;
+        }
+        if byte == b'_' && i + 1 < bytes.len() && bytes[i + 1] == b'_'
+        {
+            return false;
         }
+        i += 1;
     }
+    true
 }
 
 /// Defines an enum in which each variant maps to a single string (via a 
@@ -249,15 +505,15 @@ macro_rules! component
 /// use aframe::simple_enum;
 /// 
 /// simple_enum!
-/// (Autoplay, 
-///     Null => "null", 
-///     True => "true", 
-///     False => "false"
+/// (AnimationDirection, 
+///     Normal => "normal", 
+///     Reverse => "reverse", 
+///     Alternate => "alternate"
 /// );
 /// component_struct!
 /// (Animation,
 ///     // ...
-///     autoplay: "autoplay" Autoplay = Autoplay::Null,
+///     dir: "dir" AnimationDirection = AnimationDirection::Normal,
 ///     // ...
 /// );
 /// ```
@@ -359,7 +615,7 @@ macro_rules! complex_enum
             {
                 match self
                 {
-                    $(Self::$variant { $($field),* } => write!(f, $fmt, $($field),*)),*
+                    $(Self::$variant { $($field),* } => write!(f, $fmt, $($crate::field_string!($field)),*)),*
                 }
             }
         }
@@ -400,3 +656,39 @@ where [T]: ToOwned, <[T] as ToOwned>::Owned: std::fmt::Debug
 {
     pub const DEFAULT: List<T> = List(Cow::Borrowed(&[]));
 }
+
+impl<T: Display + ToOwned + std::fmt::Debug + 'static + Clone + PartialEq + serde::Serialize> From<Vec<T>> for List<T>
+where [T]: ToOwned<Owned = Vec<T>>
+{
+    fn from(items: Vec<T>) -> Self
+    {
+        List(Cow::Owned(items))
+    }
+}
+
+impl<T: Display + ToOwned + std::fmt::Debug + 'static + Clone + PartialEq + serde::Serialize> FromIterator<T> for List<T>
+where [T]: ToOwned<Owned = Vec<T>>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self
+    {
+        List(Cow::Owned(items.into_iter().collect()))
+    }
+}
+
+/// Allows string lists to be written without `Cow`, e.g. 
+/// `start_events: ["click"].into()`.
+impl<const N: usize> From<[&'static str; N]> for List<Cow<'static, str>>
+{
+    fn from(items: [&'static str; N]) -> Self
+    {
+        items.into_iter().map(Cow::Borrowed).collect()
+    }
+}
+
+impl From<Vec<String>> for List<Cow<'static, str>>
+{
+    fn from(items: Vec<String>) -> Self
+    {
+        items.into_iter().map(Cow::Owned).collect()
+    }
+}
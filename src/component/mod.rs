@@ -15,7 +15,7 @@
 //! pub trait Component: Display + std::fmt::Debug + std::any::Any
 //! {
 //!     fn clone(&self) -> Box<dyn Component>;
-//!     fn eq(&self, other: &'static dyn Component) -> bool;
+//!     fn eq(&self, other: &dyn Component) -> bool;
 //!     fn as_map(&self) -> HashMap<Cow<'static, str>, Cow<'static, str>>;
 //! }
 //!
@@ -33,11 +33,17 @@
 //! A `ComponentReg` is slightly more complicated, but details on its low-level
 //! API may be added here at a later date.
 
+#[cfg(feature = "web")]
 mod register;
 mod instance;
+#[cfg(feature = "community-components")]
+mod community;
 
+#[cfg(feature = "web")]
 pub use register::*;
 pub use instance::*;
+#[cfg(feature = "community-components")]
+pub use community::*;
 
 use std::borrow::Cow;
 use crate::utils::*;
@@ -55,10 +61,97 @@ pub fn cmp_to_attr((name, cmp): &(Cow<'static, str>, Box<dyn Component>)) -> Att
     }
 }
 
+/// A component given only as its property string, for components which have 
+/// no component struct. The string is written to the attribute unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawComponent(pub Cow<'static, str>);
+impl std::fmt::Display for RawComponent
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        write!(f, "{}", self.0)
+    }
+}
+impl Component for RawComponent
+{
+    fn clone(&self) -> Box<dyn Component>
+    {
+        Box::new(Clone::clone(self))
+    }
+    fn eq(&self, other: &dyn Component) -> bool
+    {
+        (other as &dyn std::any::Any)
+            .downcast_ref::<RawComponent>()
+            .is_some_and(|other| self == other)
+    }
+    fn as_map(&self) -> std::collections::HashMap<Cow<'static, str>, Cow<'static, str>>
+    {
+        split_properties(&self.0)
+            .filter_map(|prop| prop.split_once(':'))
+            .map(|(k, v)| (k.trim().to_owned().into(), v.trim().to_owned().into()))
+            .collect()
+    }
+}
+
+/// A component given as an ordered list of property names to values, for 
+/// components with a map schema but no component struct, such as many 
+/// third-party components. Displays as `name: value;` pairs in order.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct MapComponent(pub Cow<'static, [(Cow<'static, str>, Cow<'static, str>)]>);
+impl ConstDefault for MapComponent
+{
+    const DEFAULT: Self = MapComponent(Cow::Borrowed(&[]));
+}
+impl MapComponent
+{
+    pub fn new<K, V>(props: impl IntoIterator<Item = (K, V)>) -> Self
+    where K: Into<Cow<'static, str>>, V: Into<Cow<'static, str>>
+    {
+        Self(props.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+
+    /// The value of the named property, if present
+    pub fn get(&self, name: &str) -> Option<&str>
+    {
+        self.0.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_ref())
+    }
+}
+impl std::fmt::Display for MapComponent
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        for (k, v) in self.0.iter()
+        {
+            write!(f, "{}: {};", k, v)?;
+        }
+        Ok(())
+    }
+}
+impl Component for MapComponent
+{
+    fn clone(&self) -> Box<dyn Component>
+    {
+        Box::new(Clone::clone(self))
+    }
+    fn eq(&self, other: &dyn Component) -> bool
+    {
+        (other as &dyn std::any::Any)
+            .downcast_ref::<MapComponent>()
+            .is_some_and(|other| self == other)
+    }
+    fn as_map(&self) -> std::collections::HashMap<Cow<'static, str>, Cow<'static, str>>
+    {
+        self.0.iter().cloned().collect()
+    }
+}
+
 component_struct!
 (
     /// [animation](https://aframe.io/docs/1.6.0/components/animation.html)
-    Animation,
+    /// 
+    /// `autoplay` is an `Option<bool>`, where `None` is Aframe's `null` 
+    /// default.
+    Animation as "animation",
     property: "property" Cow<'static, str> = Cow::Borrowed(""),
     is_raw_property: "isRawProperty" bool = false,
     from: "from" Cow<'static, str> = Cow::Borrowed("null"),
@@ -68,14 +161,107 @@ component_struct!
     dur: "dur" u64 = 1000,
     easing: "easing" Easing = Easing::EaseInQuad,
     elasticity: "elasticity" u32 = 400,
-    looping: "loop" AnimationLoop = AnimationLoop::Amount{looping: 0},
+    looping: "loop" AnimationLoop = AnimationLoop::none(),
     round: "round" bool = false,
     start_events: "startEvents" List<Cow<'static, str>> = List::DEFAULT,
     pause_events: "pauseEvents" List<Cow<'static, str>> = List::DEFAULT,
     resume_events: "resumeEvents" List<Cow<'static, str>> = List::DEFAULT,
-    autoplay: "autoplay" Autoplay = Autoplay::Null,
+    autoplay: "autoplay" Option<bool> = None,
     enabled: "enabled" bool = true
 );
+impl Animation
+{
+    /// Animate `property` (e.g. `"position"` or `"material.color"`) from its 
+    /// current value to `to`.
+    pub fn new(property: impl Into<Cow<'static, str>>, to: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self { property: property.into(), to: to.into(), ..Self::DEFAULT }
+    }
+
+    /// Start the animation when any of the given events is emitted on the 
+    /// entity. Empty event names are ignored.
+    pub fn on_events(self, events: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self
+    {
+        Self { start_events: event_list(events), ..self }
+    }
+
+    /// Pause the animation when any of the given events is emitted on the 
+    /// entity. Empty event names are ignored.
+    pub fn pause_on(self, events: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self
+    {
+        Self { pause_events: event_list(events), ..self }
+    }
+
+    /// Resume the animation when any of the given events is emitted on the 
+    /// entity. Empty event names are ignored.
+    pub fn resume_on(self, events: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self
+    {
+        Self { resume_events: event_list(events), ..self }
+    }
+
+    /// Animates the color `property` (e.g. `"material.color"`) through a list 
+    /// of `(t, color)` stops over `dur` milliseconds, where `t` is the fraction
+    /// of `dur` at which the stop is reached. Aframe only animates between two
+    /// values, so this returns a chain of animations named 
+    /// `animation__<property>-<n>`, each delayed until the previous one ends.
+    /// Stops must be in `0.0..=1.0` and sorted by `t`, and at least two are 
+    /// needed.
+    pub fn color_stops(property: &str, stops: &[(f32, color::Rgb)], dur: u64) -> Result<Vec<(Cow<'static, str>, Animation)>, InvalidColorStops>
+    {
+        if stops.len() < 2
+        {
+            return Err(InvalidColorStops(format!("expected at least 2 stops, found {}", stops.len())));
+        }
+        if let Some((t, _)) = stops.iter().find(|(t, _)| !(0.0..=1.0).contains(t))
+        {
+            return Err(InvalidColorStops(format!("{} is outside of 0.0..=1.0", t)));
+        }
+        if let Some(pair) = stops.windows(2).find(|pair| pair[1].0 < pair[0].0)
+        {
+            return Err(InvalidColorStops(format!("{} comes after {}", pair[1].0, pair[0].0)));
+        }
+        let at = |t: f32| (t as f64 * dur as f64).round() as u64;
+        let prefix = property.replace('.', "-");
+        Ok(stops.windows(2)
+            .enumerate()
+            .map(|(i, pair)|
+            {
+                let ((start, from), (end, to)) = (pair[0], pair[1]);
+                let animation = Self
+                {
+                    property: Cow::Owned(property.to_owned()),
+                    from: Cow::Owned(from.to_string()),
+                    to: Cow::Owned(to.to_string()),
+                    delay: at(start),
+                    dur: at(end) - at(start),
+                    easing: Easing::Linear,
+                    ..Self::DEFAULT
+                };
+                (Cow::Owned(format!("animation__{}-{}", prefix, i)), animation)
+            })
+            .collect())
+    }
+}
+/// Returned by [`Animation::color_stops`] when the given stops are invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidColorStops(pub String);
+impl std::fmt::Display for InvalidColorStops
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        write!(f, "Invalid color stops: {}", self.0)
+    }
+}
+impl std::error::Error for InvalidColorStops {}
+/// Collects event names into a `List`, dropping any which are empty
+fn event_list(events: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> List<Cow<'static, str>>
+{
+    let events: Vec<Cow<'static, str>> = events.into_iter()
+        .map(Into::into)
+        .filter(|event| !event.trim().is_empty())
+        .collect();
+    List(Cow::Owned(events))
+}
 complex_enum!
 (
     /// [animation#loop](https://aframe.io/docs/1.6.0/components/animation.html#api_loop)
@@ -83,14 +269,35 @@ complex_enum!
     Amount "{}" => { looping: u32 },
     Forever "true" => {}
 );
-simple_enum!
-(
-    /// [animation#autoplay](https://aframe.io/docs/1.6.0/components/animation.html#api_autoplay)
-    Autoplay, 
-    Null => "null", 
-    True => "true", 
-    False => "false"
-);
+/// Aframe's `loop` is the number of times an animation *repeats* after it first
+/// plays, so `Amount { looping: 1 }` plays the animation twice. Prefer these 
+/// constructors over building the variants directly.
+impl AnimationLoop
+{
+    /// Play the animation once, without repeating. This is the default.
+    pub const fn none() -> Self
+    {
+        Self::Amount { looping: 0 }
+    }
+
+    /// Repeat the animation once, so that it plays twice in total.
+    pub const fn once() -> Self
+    {
+        Self::times(1)
+    }
+
+    /// Repeat the animation `n` times, so that it plays `n + 1` times in total.
+    pub const fn times(n: u32) -> Self
+    {
+        Self::Amount { looping: n }
+    }
+
+    /// Repeat the animation indefinitely.
+    pub const fn forever() -> Self
+    {
+        Self::Forever {}
+    }
+}
 simple_enum!
 (
     /// [animation#dir](https://aframe.io/docs/1.6.0/components/animation.html#api_dir)
@@ -99,50 +306,118 @@ simple_enum!
     Reverse => "reverse", 
     Alternate => "alternate"
 );
-simple_enum!
-(
-    /// [animation#easings](https://aframe.io/docs/1.6.0/components/animation.html#easings)
-    Easing, 
-    EaseInQuad => "easeInQuad",
-    EaseInCubic => "easeInCubic",
-    EaseInQuart => "easeInQuart",
-    EaseInQuint => "easeInQuint",
-    EaseInSine => "easeInSine",
-    EaseInExpo => "easeInExpo",
-    EaseInCirc => "easeInCirc",
-    EaseInBack => "easeInBack",
-    EaseInElastic => "easeInElastic",
-    EaseOutQuad => "easeOutQuad",
-    EaseOutCubic => "easeOutCubic",
-    EaseOutQuart => "easeOutQuart",
-    EaseOutQuint => "easeOutQuint",
-    EaseOutSine => "easeOutSine",
-    EaseOutExpo => "easeOutExpo",
-    EaseOutCirc => "easeOutCirc",
-    EaseOutBack => "easeOutBack",
-    EaseOutElastic => "easeOutElastic",
-    EaseInOutQuad => "easeInOutQuad",
-    EaseInOutCubic => "easeInOutCubic",
-    EaseInOutQuart => "easeInOutQuart",
-    EaseInOutQuint => "easeInOutQuint",
-    EaseInOutSine => "easeInOutSine",
-    EaseInOutExpo => "easeInOutExpo",
-    EaseInOutCirc => "easeInOutCirc",
-    EaseInOutBack => "easeInOutBack",
-    EaseInOutElastic => "easeInOutElastic",
-    Linear => "linear"
-);
+/// [animation#easings](https://aframe.io/docs/1.6.0/components/animation.html#easings)
+/// 
+/// Along with the named easings, the `steps(n)` and `cubicBezier(x1,y1,x2,y2)`
+/// easing functions from anime.js are supported.
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize)]
+pub enum Easing
+{
+    EaseInQuad,
+    EaseInCubic,
+    EaseInQuart,
+    EaseInQuint,
+    EaseInSine,
+    EaseInExpo,
+    EaseInCirc,
+    EaseInBack,
+    EaseInElastic,
+    EaseOutQuad,
+    EaseOutCubic,
+    EaseOutQuart,
+    EaseOutQuint,
+    EaseOutSine,
+    EaseOutExpo,
+    EaseOutCirc,
+    EaseOutBack,
+    EaseOutElastic,
+    EaseInOutQuad,
+    EaseInOutCubic,
+    EaseInOutQuart,
+    EaseInOutQuint,
+    EaseInOutSine,
+    EaseInOutExpo,
+    EaseInOutCirc,
+    EaseInOutBack,
+    EaseInOutElastic,
+    Linear,
+    Steps(u32),
+    CubicBezier(f32, f32, f32, f32)
+}
+impl std::fmt::Display for Easing
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        match self
+        {
+            Self::EaseInQuad => write!(f, "easeInQuad"),
+            Self::EaseInCubic => write!(f, "easeInCubic"),
+            Self::EaseInQuart => write!(f, "easeInQuart"),
+            Self::EaseInQuint => write!(f, "easeInQuint"),
+            Self::EaseInSine => write!(f, "easeInSine"),
+            Self::EaseInExpo => write!(f, "easeInExpo"),
+            Self::EaseInCirc => write!(f, "easeInCirc"),
+            Self::EaseInBack => write!(f, "easeInBack"),
+            Self::EaseInElastic => write!(f, "easeInElastic"),
+            Self::EaseOutQuad => write!(f, "easeOutQuad"),
+            Self::EaseOutCubic => write!(f, "easeOutCubic"),
+            Self::EaseOutQuart => write!(f, "easeOutQuart"),
+            Self::EaseOutQuint => write!(f, "easeOutQuint"),
+            Self::EaseOutSine => write!(f, "easeOutSine"),
+            Self::EaseOutExpo => write!(f, "easeOutExpo"),
+            Self::EaseOutCirc => write!(f, "easeOutCirc"),
+            Self::EaseOutBack => write!(f, "easeOutBack"),
+            Self::EaseOutElastic => write!(f, "easeOutElastic"),
+            Self::EaseInOutQuad => write!(f, "easeInOutQuad"),
+            Self::EaseInOutCubic => write!(f, "easeInOutCubic"),
+            Self::EaseInOutQuart => write!(f, "easeInOutQuart"),
+            Self::EaseInOutQuint => write!(f, "easeInOutQuint"),
+            Self::EaseInOutSine => write!(f, "easeInOutSine"),
+            Self::EaseInOutExpo => write!(f, "easeInOutExpo"),
+            Self::EaseInOutCirc => write!(f, "easeInOutCirc"),
+            Self::EaseInOutBack => write!(f, "easeInOutBack"),
+            Self::EaseInOutElastic => write!(f, "easeInOutElastic"),
+            Self::Linear => write!(f, "linear"),
+            Self::Steps(steps) => write!(f, "steps({})", steps),
+            Self::CubicBezier(x1, y1, x2, y2) => write!(f, "cubicBezier({},{},{},{})", x1, y1, x2, y2)
+        }
+    }
+}
 component_struct!
 (
     /// [background](https://aframe.io/docs/1.6.0/components/background.html)
-    Background, 
-    color: "color" color::Rgb = color::BLACK,
-    transparent: "transparent" bool = false
+    Background as "background", 
+    fill: "" BackgroundFill = BackgroundFill::Color { color: color::BLACK }
+);
+impl Background
+{
+    /// An opaque background of the given color
+    pub const fn color(color: color::Rgb) -> Self
+    {
+        Self { fill: BackgroundFill::Color { color } }
+    }
+
+    /// A transparent background, for compositing the scene over the page or 
+    /// over the camera feed in AR
+    pub const fn transparent() -> Self
+    {
+        Self { fill: BackgroundFill::Transparent {} }
+    }
+}
+complex_enum!
+(
+    /// [background#properties](https://aframe.io/docs/1.6.0/components/background.html#properties)
+    /// 
+    /// Aframe ignores `color` when `transparent` is set, so the two are 
+    /// mutually exclusive here.
+    BackgroundFill,
+    Color "color: {}" => { color: color::Rgb },
+    Transparent "transparent: true" => {}
 );
 component_struct!
 {
     /// [camera](https://aframe.io/docs/1.6.0/components/camera.html)
-    Camera,
+    Camera as "camera",
     active: "active" bool = true,
     far: "far" u32 = 10000,
     fov: "fov" f32 = 80.0,
@@ -153,7 +428,7 @@ component_struct!
 component_struct!
 (
     /// [cursor](https://aframe.io/docs/1.6.0/components/cursor.html)
-    Cursor, 
+    Cursor as "cursor", 
     // TODO: True event handling
     down_events: "downEvents" List<Cow<'static, str>> = List::DEFAULT,
     fuse: "fuse" bool = false,
@@ -172,7 +447,7 @@ simple_enum!
 component_struct!
 (
     /// [daydream-controls](https://aframe.io/docs/1.6.0/components/daydream-controls.html)
-    DaydreamControls, 
+    DaydreamControls as "daydream-controls", 
     arm_model: "armModel" bool = true,
     botton_color: "bottonColor" color::Rgb = color::BLACK,
     button_touched_color: "buttonTouchedColor" color::Rgb = color::GREY47,
@@ -192,7 +467,7 @@ simple_enum!
 component_struct!
 (
     /// [device-orientation-permission-ui](https://aframe.io/docs/1.6.0/components/device-orientation-permission-ui.html)
-    DeviceOrientationPermissionUI, 
+    DeviceOrientationPermissionUI as "device-orientation-permission-ui", 
     enabled: "enabled" bool = true,
     deny_button_text: "denyButtonText" Cow<'static, str> = Cow::Borrowed("Deny"),
     allow_button_text: "allowButtonText" Cow<'static, str> = Cow::Borrowed("Allow"),
@@ -204,12 +479,16 @@ component_struct!
 component_struct!
 (
     /// [embedded](https://aframe.io/docs/1.6.0/components/embedded.html)
-    Embedded
+    Embedded as "embedded"
 );
 component_struct!
 (
     /// [fog](https://aframe.io/docs/1.6.0/components/fog.html)
-    Fog, 
+    /// 
+    /// An empty `fog` attribute enables fog with its defaults, so fog on a live
+    /// scene is turned off by removing the component entirely, with 
+    /// [`sys::disable_fog`](crate::sys::disable_fog).
+    Fog as "fog", 
     fog_type: "" FogType = FogType::Linear { near: 1, far: 1000 },
     color: "color" color::Rgb = color::BLACK
 );
@@ -223,7 +502,7 @@ complex_enum!
 component_struct!
 (
     /// [gearvr-controls](https://aframe.io/docs/1.6.0/components/gearvr-controls.html)
-    GearVRControls, 
+    GearVRControls as "gearvr-controls", 
     arm_model: "armModel" bool = true,
     button_color: "buttonColor" color::Rgb = color::BLACK,
     button_touched_color: "buttonTouchedColor" color::Rgb = color::GREY47,
@@ -235,7 +514,7 @@ component_struct!
 component_struct!
 (
     /// [geometry](https://aframe.io/docs/1.6.0/components/geometry.html)
-    Geometry,
+    Geometry as "geometry",
     primitive: "" GeometryPrimitive = GeometryPrimitive::Box
     {
         width: 1.0,
@@ -245,11 +524,15 @@ component_struct!
         segments_height: 1,
         segments_depth: 1,
     },
+    buffer: "buffer" bool = true,
     skip_cache: "skipCache" bool = false
 );
 complex_enum!
 (
     /// [geometry#built-in-geometries](https://aframe.io/docs/1.6.0/components/geometry.html#built-in-geometries)
+    /// 
+    /// For the polyhedra, `detail` is the number of times each face is 
+    /// subdivided. Aframe's default is `0`, which gives the flat low-poly look.
     GeometryPrimitive, 
     Box
     "primitive: box; width: {}; height: {}; depth: {}; segmentsWidth: {}; \
@@ -299,9 +582,11 @@ complex_enum!
         theta_length: f32
     },
     Dodecahedron
-    "primitive: dodecahedron; radius: {}" => { radius: f32 },
+    "primitive: dodecahedron; radius: {}; detail: {}" => { radius: f32, detail: u32 },
+    Icosahedron
+    "primitive: icosahedron; radius: {}; detail: {}" => { radius: f32, detail: u32 },
     Octahedron
-    "primitive: octahedron; radius: {}" => { radius: f32 },
+    "primitive: octahedron; radius: {}; detail: {}" => { radius: f32, detail: u32 },
     Plane
     "primitive: plane; width: {}; height: {}; \
     segmentsWidth: {}; segmentsHeight: {}" =>
@@ -337,7 +622,7 @@ complex_enum!
         theta_length: f32
     },
     Tetrahedron
-    "primitive: tetrahedron; radius: {}" => { radius: f32 },
+    "primitive: tetrahedron; radius: {}; detail: {}" => { radius: f32, detail: u32 },
     Torus
     "primitive: torus; radius: {}; radiusTubular: {}; \
     segmentsRadial: {}; segmentsTubular: {}; arc: {}" =>
@@ -377,13 +662,13 @@ complex_enum!
 component_struct!
 (
     /// [gltf-model](https://aframe.io/docs/1.6.0/components/gltf-model.html)
-    GltfModel, 
-    string: "" Cow<'static, str> = Cow::Borrowed("")
+    GltfModel as "gltf-model" :alt "{}", 
+    src: "src" Selector = Selector::Raw(Cow::Borrowed(""))
 );
 component_struct!
 (
     /// [hand-controls](https://aframe.io/docs/1.6.0/components/hand-controls.html)
-    HandControls, 
+    HandControls as "hand-controls", 
     color: "color" color::Rgb = color::WHITE,
     hand: "hand" Hand = Hand::Left,
     hand_model_style: "handModelStyle" HandModelStyle = HandModelStyle::LowPoly
@@ -399,7 +684,7 @@ simple_enum!
 component_struct!
 (
     /// [hand-controls](https://aframe.io/docs/1.6.0/components/hand-controls.html)
-    HandTrackingControls, 
+    HandTrackingControls as "hand-tracking-controls", 
     hand: "hand" Hand = Hand::Left,
     model_color: "modelColor" color::Rgb = color::WHITE,
     model_style: "modelStyle" ModelStyle = ModelStyle::Mesh
@@ -414,13 +699,21 @@ simple_enum!
 component_struct!
 (
     /// [laser-controls](https://aframe.io/docs/1.6.0/components/laser-controls.html)
-    LaserControls, 
+    LaserControls as "laser-controls", 
     enter_vr: "enterVR" bool = true
 );
 component_struct!
+(
+    /// [inspector](https://aframe.io/docs/1.6.0/components/inspector.html)
+    /// 
+    /// Open the inspector with `<ctrl> + <alt> + i`, or with `sys::open_inspector`.
+    Inspector as "inspector",
+    url: "url" Cow<'static, str> = Cow::Borrowed("https://cdn.jsdelivr.net/npm/aframe-inspector@1.6.x/dist/aframe-inspector.min.js")
+);
+component_struct!
 (
     /// [keyboard-shortcuts](https://aframe.io/docs/1.6.0/components/keyboard-shortcuts.html)
-    KeyboardShortcuts, 
+    KeyboardShortcuts as "keyboard-shortcuts", 
     hand: "hand" Hand = Hand::Left,
     model: "model" bool = true,
     default_model_color: "defaultModelColor" color::Rgb = color::WHITE
@@ -428,7 +721,7 @@ component_struct!
 component_struct!
 (
     /// [layer](https://aframe.io/docs/1.6.0/components/layer.html)
-    Layer, 
+    Layer as "layer", 
     layer_type: "type" LayerType = LayerType::Quad,
     src: "src" Cow<'static, str> = Cow::Borrowed(""),
     rotate_cubemap: "rotateCubemap" bool = false
@@ -444,11 +737,66 @@ simple_enum!
 component_struct!
 (
     /// [light](https://aframe.io/docs/1.6.0/components/light.html)
-    Light, 
+    Light as "light", 
     light_type: "" LightType = LightType::Directional { shadow: OptionalDirectionalShadow::NoCast{} },
     color: "color" color::Rgb = color::WHITE,
     intensity: "intensity" f32 = 1.0
 );
+impl Light
+{
+    /// An ambient light, which lights everything in the scene equally.
+    pub fn ambient(color: color::Rgb, intensity: f32) -> Self
+    {
+        Self { light_type: LightType::Ambient {}, color, intensity }
+    }
+
+    /// A directional light, optionally casting shadows with the default shadow
+    /// camera.
+    pub fn directional(color: color::Rgb, intensity: f32, cast_shadow: bool) -> Self
+    {
+        let shadow = if cast_shadow 
+        { 
+            OptionalDirectionalShadow::Cast { shadow: DirectionalShadow::DEFAULT } 
+        } 
+        else 
+        { 
+            OptionalDirectionalShadow::NoCast {} 
+        };
+        Self { light_type: LightType::Directional { shadow }, color, intensity }
+    }
+
+    /// A point light which doesn't cast shadows. A `distance` of `0` means the
+    /// light never fades out.
+    pub fn point(color: color::Rgb, intensity: f32, distance: f32) -> Self
+    {
+        let light_type = LightType::Point { decay: 1.0, distance, shadow: OptionalLocalShadow::NoCast {} };
+        Self { light_type, color, intensity }
+    }
+
+    /// A spot light with the given cone `angle` in degrees and `penumbra` 
+    /// (between `0` and `1`), optionally casting shadows with the default 
+    /// shadow camera. The light points down its entity's negative z axis.
+    pub fn spot(color: color::Rgb, intensity: f32, angle: i32, penumbra: f32, cast_shadow: bool) -> Self
+    {
+        Self::spot_at(Selector::Raw(Cow::Borrowed("")), color, intensity, angle, penumbra, cast_shadow)
+    }
+
+    /// A spot light like [`Light::spot`], aimed at the entity matched by 
+    /// `target` rather than down its own negative z axis.
+    pub fn spot_at(target: Selector, color: color::Rgb, intensity: f32, angle: i32, penumbra: f32, cast_shadow: bool) -> Self
+    {
+        let shadow = if cast_shadow 
+        { 
+            OptionalLocalShadow::Cast { shadow: LocalShadow::DEFAULT } 
+        } 
+        else 
+        { 
+            OptionalLocalShadow::NoCast {} 
+        };
+        let light_type = LightType::Spot { angle, decay: 1.0, distance: 0.0, penumbra, target, shadow };
+        Self { light_type, color, intensity }
+    }
+}
 complex_enum!
 (
     /// [light-types](https://aframe.io/docs/1.6.0/components/light.html#light-types)
@@ -468,7 +816,7 @@ complex_enum!
         decay: f32,
         distance: f32,
         penumbra: f32,
-        target: Cow<'static, str>,
+        target: Selector,
         shadow: OptionalLocalShadow
     }
 );
@@ -499,6 +847,40 @@ component_struct!
     shadow_camera_right: "shadowCameraRight" f32 = 5.0,
     shadow_camera_top: "shadowCameraTop" f32 = 5.0
 );
+impl LocalShadow
+{
+    /// Sets the shadow map size, rounding each dimension to the nearest power
+    /// of two. three.js samples power-of-two shadow maps most reliably; other
+    /// sizes can produce blurry shadows on some GPUs.
+    pub const fn with_map_size(self, width: u32, height: u32) -> Self
+    {
+        Self { shadow_map_width: nearest_power_of_two(width), shadow_map_height: nearest_power_of_two(height), ..self }
+    }
+}
+impl DirectionalShadow
+{
+    /// Sets the shadow map size, rounding each dimension to the nearest power
+    /// of two. See [`LocalShadow::with_map_size`].
+    pub const fn with_map_size(self, width: u32, height: u32) -> Self
+    {
+        Self { shadow_map_width: nearest_power_of_two(width), shadow_map_height: nearest_power_of_two(height), ..self }
+    }
+}
+/// Rounds `n` to the nearest power of two, rounding up when halfway between
+const fn nearest_power_of_two(n: u32) -> u32
+{
+    if n <= 1
+    {
+        return 1;
+    }
+    if n > 1 << 31
+    {
+        return 1 << 31;
+    }
+    let upper = n.next_power_of_two();
+    let lower = upper >> 1;
+    if n - lower < upper - n { lower } else { upper }
+}
 complex_enum!
 (
     /// Shadow used for point and spot lights
@@ -516,7 +898,7 @@ complex_enum!
 component_struct!
 (
     /// [line](https://aframe.io/docs/1.6.0/components/line.html)
-    Line, 
+    Line as "line", 
     start: "start" Vector3 = Vector3::DEFAULT,
     end: "end" Vector3 = Vector3::DEFAULT,
     color: "color" color::Rgb = color::Rgb::new(116, 190, 193),
@@ -526,7 +908,7 @@ component_struct!
 component_struct!
 (
     /// [link](https://aframe.io/docs/1.6.0/components/link.html)
-    Link, 
+    Link as "link", 
     background_color: "backgroundColor" color::Rgb = color::RED,
     border_color: "borderColor" color::Rgb = color::WHITE,
     highlighted: "highlighted" bool = false,
@@ -539,10 +921,18 @@ component_struct!
     title_color: "titleColor" color::Rgb = color::WHITE,
     visual_aspect_enabled: "visualAspectEnabled" bool = true
 );
+impl Link
+{
+    /// A link to the page at `href`
+    pub fn new(href: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self { href: href.into(), ..Self::DEFAULT }
+    }
+}
 component_struct!
 (
     /// [loading-screen](https://aframe.io/docs/1.6.0/components/loading-screen.html)
-    LoadingScreen, 
+    LoadingScreen as "loading-screen", 
     dots_color: "dotsColor" color::Rgb = color::WHITE,
     background_color: "backgroundColor" color::Rgb = color::Rgb::new(36, 202, 255),
     enabled: "enabled" bool = true
@@ -550,7 +940,7 @@ component_struct!
 component_struct!
 {
     /// [look-controls](https://aframe.io/docs/1.6.0/components/look-controls.html)
-    LookControls,
+    LookControls as "look-controls",
     enabled: "enabled" bool = true,
     hmd_enabled: "hmdEnabled" bool = true,
     reverse_mouse_drag: "reverseMouseDrag" bool = false,
@@ -563,7 +953,7 @@ component_struct!
 component_struct!
 {
     /// [magicleap-controls](https://aframe.io/docs/1.6.0/components/magicleap-controls.html)
-    MagicLeapControls,
+    MagicLeapControls as "magicleap-controls",
     hand: "hand" Hand = Hand::None,
     model: "model" bool = true,
     orientation_offset: "orientationOffset" Vector3 = Vector3::DEFAULT
@@ -571,7 +961,7 @@ component_struct!
 component_struct!
 (
     /// [material](https://aframe.io/docs/1.6.0/components/material.html)
-    Material, 
+    Material as "material", 
     alpha_test: "alphaTest" f32 = 0.0,
     depth_test: "depthTest" bool = true,
     flat_shading: "flatShading" bool = false,
@@ -579,13 +969,16 @@ component_struct!
     offset: "offset" Vector2 = Vector2 { x: 0.0, y: 0.0 },
     opacity: "opacity" f32 = 1.0,
     repeat: "repeat" Vector2 = Vector2 { x: 1.0, y: 1.0 },
-    shader: "shader" Cow<'static, str> = Cow::Borrowed("standard"),
+    shader: "shader" MaterialShader = MaterialShader::Standard,
     side: "side" MaterialSide = MaterialSide::Front,
     transparent: "transparent" bool = false,
     vertex_colors: "vertexColors" VertexColors = VertexColors::None,
     visible: "visible" bool = true,
     blending: "blending" Blending = Blending::Normal,
     dithering: "dithering" bool = true,
+    wireframe: "wireframe" bool = false,
+    wireframe_linewidth: "wireframeLinewidth" f32 = 2.0,
+    src: "src" MaterialSrc = MaterialSrc::None,
     props: "" MaterialProps = MaterialProps::DEFAULT
 );
 simple_enum!
@@ -615,6 +1008,51 @@ simple_enum!
     Multiply => "multiply"
 );
 
+/// [material#shader](https://aframe.io/docs/1.6.0/components/material.html#properties_shader)
+/// 
+/// One of Aframe's built-in shaders, or the name of a shader registered with 
+/// [`Shader::register`](crate::Shader::register).
+#[derive(Clone, PartialEq, Debug)]
+pub enum MaterialShader
+{
+    Standard,
+    Flat,
+    Sdf,
+    Msdf,
+    Portal,
+    Custom(Cow<'static, str>)
+}
+impl MaterialShader
+{
+    /// A custom shader registered under the given name
+    pub fn custom(name: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self::Custom(name.into())
+    }
+}
+impl std::fmt::Display for MaterialShader
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        let name = match self
+        {
+            Self::Standard => crate::shader::STANDARD,
+            Self::Flat => crate::shader::FLAT,
+            Self::Sdf => crate::shader::SDF,
+            Self::Msdf => crate::shader::MSDF,
+            Self::Portal => crate::shader::PORTAL,
+            Self::Custom(name) => name
+        };
+        write!(f, "{}", name)
+    }
+}
+impl serde::Serialize for MaterialShader
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
 /// Additional properties for the Material component. Contains a slice or vector
 /// of property names to property values.
 #[derive(Debug, Clone, PartialEq, serde::Serialize)]
@@ -635,17 +1073,105 @@ impl std::fmt::Display for MaterialProps
         Ok(())
     }
 }
+/// [material#src](https://aframe.io/docs/1.6.0/components/material.html#properties_src)
+/// 
+/// The image or video texture of a material: either a reference to an asset, 
+/// which can be taken directly from an `Asset`, `Image` or `Video` 
+/// (`src: (&image).into()`), or an inline url.
+#[derive(Clone, PartialEq, Debug)]
+pub enum MaterialSrc
+{
+    None,
+    Asset(Selector),
+    Url(Cow<'static, str>)
+}
+impl MaterialSrc
+{
+    /// A texture loaded from the given url rather than from an asset
+    pub fn url(url: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self::Url(url.into())
+    }
+}
+impl std::fmt::Display for MaterialSrc
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        match self
+        {
+            Self::None => Ok(()),
+            Self::Asset(selector) => write!(f, "{}", selector),
+            Self::Url(url) => write!(f, "url({})", url)
+        }
+    }
+}
+impl serde::Serialize for MaterialSrc
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
+impl From<Selector> for MaterialSrc
+{
+    fn from(selector: Selector) -> Self
+    {
+        Self::Asset(selector)
+    }
+}
+impl From<&crate::Asset> for MaterialSrc
+{
+    fn from(asset: &crate::Asset) -> Self
+    {
+        Self::Asset(Selector::id(asset.id().clone()))
+    }
+}
+impl From<&crate::Image> for MaterialSrc
+{
+    fn from(image: &crate::Image) -> Self
+    {
+        Self::Asset(Selector::id(image.id.clone()))
+    }
+}
+impl From<&crate::Video> for MaterialSrc
+{
+    fn from(video: &crate::Video) -> Self
+    {
+        Self::Asset(Selector::id(video.id.clone()))
+    }
+}
+component_struct!
+(
+    /// [obb-collider](https://aframe.io/docs/1.6.0/components/obb-collider.html)
+    /// 
+    /// Emits `obbcollisionstarted` and `obbcollisionended` on entities whose 
+    /// oriented bounding boxes start or stop intersecting.
+    ObbCollider as "obb-collider", 
+    size: "size" f32 = 0.0,
+    tracked_object_3d: "trackedObject3D" Cow<'static, str> = Cow::Borrowed(""),
+    minimum_collider_dimension: "minimumColliderDimension" f32 = 0.02,
+    center_model: "centerModel" bool = false
+);
 component_struct!
 (
     /// [obj-model](https://aframe.io/docs/1.6.0/components/obj-model.html)
-    ObjModel, 
+    ObjModel as "obj-model", 
     obj: "obj" Cow<'static, str> = Cow::Borrowed(""),
     mtl: "mtl" Cow<'static, str> = Cow::Borrowed("")
 );
+impl ObjModel
+{
+    /// An OBJ model with its MTL material, each given as a url or asset 
+    /// selector. `mtl` may be empty.
+    pub fn new(obj: impl Into<Cow<'static, str>>, mtl: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self { obj: obj.into(), mtl: mtl.into() }
+    }
+}
 component_struct!
 (
     /// [oculus-go-controls](https://aframe.io/docs/1.6.0/components/oculus-go-controls.html)
-    OculusGoControls, 
+    OculusGoControls as "oculus-go-controls", 
     arm_model: "armModel" bool = true,
     button_color: "buttonColor" color::Rgb = color::BLACK,
     button_touched_color: "buttonTouchedColor" color::Rgb = color::GREY47,
@@ -657,7 +1183,7 @@ component_struct!
 component_struct!
 (
     /// [oculus-touch-controls](https://aframe.io/docs/1.6.0/components/oculus-touch-controls.html)
-    OculusTouchControls,
+    OculusTouchControls as "oculus-touch-controls",
     hand: "hand" Hand = Hand::Left,
     model: "model" bool = true,
     orientation_offset: "orientationOffset" Vector3 = Vector3::DEFAULT
@@ -665,8 +1191,8 @@ component_struct!
 component_struct!
 (
     /// [pool](https://aframe.io/docs/1.6.0/components/pool.html)
-    Pool,
-    container: "container" Cow<'static, str> = Cow::Borrowed(""),
+    Pool as "pool",
+    container: "container" Selector = Selector::Raw(Cow::Borrowed("")),
     dynamic: "dynamic" bool = false,
     mixin: "mixin" Cow<'static, str> = Cow::Borrowed(""),
     size: "size" u32 = 0
@@ -674,12 +1200,12 @@ component_struct!
 component_struct!
 (
     /// [position](https://aframe.io/docs/1.6.0/components/position.html)
-    Position :alt "{} {} {}", x: "x" f32 = 0.0, y: "y" f32 = 0.0, z: "z" f32 = 0.0
+    Position as "position" :alt "{} {} {}", x: "x" f32 = 0.0, y: "y" f32 = 0.0, z: "z" f32 = 0.0
 );
 component_struct!
 {
     /// [renderer](https://aframe.io/docs/1.6.0/components/renderer.html)
-    Renderer,
+    Renderer as "renderer",
     antialias: "antialias" Antialias = Antialias::Auto,
     color_management: "colorManagement" bool = false,
     high_refresh_rate: "highRefreshRate" bool = false,
@@ -690,7 +1216,10 @@ component_struct!
     max_canvas_height: "maxCanvasHeight" u32 = 1920,
     logarithmic_depth_buffer: "logarithmicDepthBuffer" LogarithmicDepthBuffer = LogarithmicDepthBuffer::Auto,
     precision: "precision" Precision = Precision::High,
-    alpha: "alpha" bool = true
+    alpha: "alpha" bool = true,
+    tone_mapping: "toneMapping" ToneMapping = ToneMapping::No,
+    exposure: "exposure" f32 = 1.0,
+    anisotropy: "anisotropy" u32 = 1
 }
 simple_enum!
 {
@@ -701,6 +1230,16 @@ simple_enum!
     False => "false"
 }
 simple_enum!
+{
+    /// [renderer#tonemapping](https://aframe.io/docs/1.6.0/components/renderer.html#tonemapping)
+    ToneMapping,
+    No => "no",
+    ACESFilmic => "ACESFilmic",
+    Linear => "linear",
+    Reinhard => "reinhard",
+    Cineon => "cineon"
+}
+simple_enum!
 {
     /// Whether to use a logarithmic depth buffer.
     LogarithmicDepthBuffer,
@@ -719,20 +1258,33 @@ simple_enum!
 component_struct!
 {
     /// [raycaster](https://aframe.io/docs/1.6.0/components/raycaster.html)
-    RayCaster,
+    RayCaster as "raycaster",
     auto_refresh: "autoRefresh" bool = true,
-    direction: "direction" Vector3 = Vector3 { x: 0.0, y: 0.0, z: -1.0 },
+    direction: "direction" Vector3 = Vector3::forward(),
     enabled: "enabled" bool = true,
     far: "far" DistancePlane = DistancePlane::Infinity{},
     interval: "interval" u32 = 0,
     line_color: "lineColor" color::Rgb = color::WHITE,
     line_opacity: "lineOpacity" color::Rgb = color::WHITE,
     near: "near" DistancePlane = DistancePlane::Distance{distance: 0.0},
-    objects: "objects" List<Cow<'static, str>> = List(Cow::Borrowed(&[Cow::Borrowed("null")])),
+    objects: "objects" List<Selector> = List(Cow::Borrowed(&[Selector::Raw(Cow::Borrowed("null"))])),
     origin: "origin" Vector3 = Vector3 { x: 0.0, y: 0.0, z: 0.0 },
     show_line: "showLine" bool = false,
     use_world_coordinates: "useWorldCoordinates" bool = false
 }
+impl RayCaster
+{
+    /// Creates a raycaster which only tests for intersections with entities
+    /// matching the given selectors. If `selectors` is empty, `objects` is left
+    /// at its default, which A-Frame represents with the `"null"` sentinel and
+    /// interprets as "test against every entity in the scene".
+    pub fn targeting(selectors: impl IntoIterator<Item = Selector>) -> Self
+    {
+        let selectors: Vec<Selector> = selectors.into_iter().collect();
+        let objects = if selectors.is_empty() { Self::DEFAULT.objects } else { List(Cow::Owned(selectors)) };
+        Self { objects, ..Self::DEFAULT }
+    }
+}
 complex_enum! 
 {
     /// [raycaster#far](https://aframe.io/docs/1.6.0/components/raycaster.html#properties_far)
@@ -743,58 +1295,115 @@ complex_enum!
 component_struct!
 (
     /// [rotation](https://aframe.io/docs/1.6.0/components/rotation.html)
-    Rotation :alt "{} {} {}", x: "x" f32 = 0.0, y: "y" f32 = 0.0, z: "z" f32 = 0.0
+    Rotation as "rotation" :alt "{} {} {}", x: "x" f32 = 0.0, y: "y" f32 = 0.0, z: "z" f32 = 0.0
 );
 component_struct!
 (
     /// [scale](https://aframe.io/docs/1.6.0/components/scale.html)
-    Scale :alt "{} {} {}", x: "x" f32 = 1.0, y: "y" f32 = 1.0, z: "z" f32 = 1.0
+    Scale as "scale" :alt "{} {} {}", x: "x" f32 = 1.0, y: "y" f32 = 1.0, z: "z" f32 = 1.0
 );
+/// Bundles the [Position], [Rotation] and [Scale] components, which are set on
+/// almost every entity. The defaults match those of the individual components.
+/// ```ignore
+/// let mut components = component!
+/// (
+///     Transform, 
+///     position: component!(Position, y: 1.5), 
+///     rotation: component!(Rotation, y: 45.0)
+/// ).into_components();
+/// components.push(("geometry".into(), Box::new(component!(Geometry))));
+/// let entity = Entity::with_components(components);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transform
+{
+    pub position: Position,
+    pub rotation: Rotation,
+    pub scale: Scale
+}
+impl ConstDefault for Transform
+{
+    const DEFAULT: Self = Self 
+    { 
+        position: Position::DEFAULT, 
+        rotation: Rotation::DEFAULT, 
+        scale: Scale::DEFAULT 
+    };
+}
+impl Transform
+{
+    /// Expand into the `position`, `rotation` and `scale` components
+    pub fn into_components(self) -> Vec<(Cow<'static, str>, Box<dyn Component>)>
+    {
+        vec!
+        (
+            (Cow::Borrowed("position"), Box::new(self.position)),
+            (Cow::Borrowed("rotation"), Box::new(self.rotation)),
+            (Cow::Borrowed("scale"), Box::new(self.scale))
+        )
+    }
+}
 component_struct!
 {
     /// [screenshot](https://aframe.io/docs/1.6.0/components/screenshot.html)
-    Screenshot,
+    Screenshot as "screenshot",
     width: "width" u32 = 4096,
     height: "height" u32 = 2048
 }
+simple_enum!
+(
+    /// [screenshot#capturing](https://aframe.io/docs/1.6.0/components/screenshot.html)
+    Projection, 
+    Perspective => "perspective", 
+    Equirectangular => "equirectangular"
+);
 component_struct!
 (
     /// [shadow](https://aframe.io/docs/1.6.0/components/shadow.html)
-    Shadow, 
+    Shadow as "shadow", 
     cast: "cast" bool = true,
     receive: "receive" bool = true
 );
 component_struct!
 (
     /// [sound](https://aframe.io/docs/1.6.0/components/sound.html)
-    Sound, 
+    Sound as "sound", 
     src: "src" Cow<'static, str> = Cow::Borrowed(""),
     autoplay: "autoplay" bool = false,
     positional: "positional" bool = true,
     volume: "volume" f32 = 1.0,
     looping: "loop" bool = false
 );
+impl Sound
+{
+    /// A sound playing the audio at `src`, given as a url or asset selector
+    pub fn new(src: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self { src: src.into(), ..Self::DEFAULT }
+    }
+}
 component_struct!
 (
     /// [stats](https://aframe.io/docs/1.6.0/components/stats.html)
-    Stats
+    Stats as "stats"
 );
 component_struct!
 (
     /// [text](https://aframe.io/docs/1.6.0/components/text.html)
-    Text, 
+    Text as "text", 
     align: "align" TextAlignment = TextAlignment::Left,
     alpha_test: "alphaTest" f32 = 0.5,
     anchor: "anchor" TextAnchor = TextAnchor::Center,
     baseline: "baseline" TextBaseline = TextBaseline::Center,
     color: "color" color::Rgb = color::WHITE,
-    font: "font" Cow<'static, str> = Cow::Borrowed("default"),
+    font: "font" Font = Font::Roboto,
     font_image: "fontImage" Cow<'static, str> = Cow::Borrowed("default"),
     height: "height" u32 = 0,
     letter_spacing: "letterSpacing" u32 = 0,
     line_height: "lineHeight" u32 = 0,
+    negate: "negate" bool = true,
     opacity: "opacity" f64 = 1.0,
-    shader: "shader" Cow<'static, str> = Cow::Borrowed("sdf"),
+    shader: "shader" TextShader = TextShader::Sdf,
     side: "side" TextSide = TextSide::Front,
     tab_size: "tabSize" u32 = 4,
     transparent: "transparent" bool = true,
@@ -806,6 +1415,66 @@ component_struct!
     x_offset: "xOffset" f64 = 0.0,
     y_offset: "yOffset" f64 = 0.001
 );
+impl Text
+{
+    /// Text displaying `value`
+    pub fn new(value: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self { value: value.into(), ..Self::DEFAULT }
+    }
+}
+/// [text#fonts](https://aframe.io/docs/1.6.0/components/text.html#fonts)
+/// 
+/// One of Aframe's stock fonts, or the url of a custom font's JSON file.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Font
+{
+    AileronSemiBold,
+    DejaVu,
+    Exo2Bold,
+    Exo2SemiBold,
+    KelsonSans,
+    Monoid,
+    MozillaVr,
+    Roboto,
+    SourceCodePro,
+    Custom(Cow<'static, str>)
+}
+impl Font
+{
+    /// A custom font loaded from the given url
+    pub fn custom(url: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self::Custom(url.into())
+    }
+}
+impl std::fmt::Display for Font
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        let font = match self
+        {
+            Self::AileronSemiBold => "aileronsemibold",
+            Self::DejaVu => "dejavu",
+            Self::Exo2Bold => "exo2bold",
+            Self::Exo2SemiBold => "exo2semibold",
+            Self::KelsonSans => "kelsonsans",
+            Self::Monoid => "monoid",
+            Self::MozillaVr => "mozillavr",
+            Self::Roboto => "roboto",
+            Self::SourceCodePro => "sourcecodepro",
+            Self::Custom(url) => url
+        };
+        write!(f, "{}", font)
+    }
+}
+impl serde::Serialize for Font
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
 simple_enum!
 {
     /// Multi-line text alignment (left, center, right).
@@ -832,6 +1501,13 @@ simple_enum!
     Bottom => "bottom"
 }
 simple_enum!
+{
+    /// Shader used to render the text (sdf, msdf).
+    TextShader,
+    Sdf => "sdf",
+    Msdf => "msdf"
+}
+simple_enum!
 {
     /// Side to render. (front, back, double)
     TextSide,
@@ -850,25 +1526,25 @@ simple_enum!
 component_struct!
 (
     /// [tracked-controls](https://aframe.io/docs/1.6.0/components/tracked-controls.html)
-    TrackedControls, 
+    TrackedControls as "tracked-controls", 
     arm_model: "armModel" bool = true,
     auto_hide: "autoHide" bool = true,
     controller: "controller" u32 = 0,
     id: "id" Cow<'static, str> = Cow::Borrowed(""),
     id_prefix: "idPrefix" Cow<'static, str> = Cow::Borrowed(""),
-    head_element: "headElement" Cow<'static, str> = Cow::Borrowed(""),
+    head_element: "headElement" Selector = Selector::Raw(Cow::Borrowed("")),
     hand: "hand" Hand = Hand::Right,
     orientation_offset: "orientationOffset" Vector3 = Vector3::DEFAULT
 );
 component_struct!
 (
     /// [visible](https://aframe.io/docs/1.6.0/components/visible.html)
-    Visible :alt "{}", visible: "visible" bool = true
+    Visible as "visible" :alt "{}", visible: "visible" bool = true
 );
 component_struct!
 (
     /// [vive-controls](https://aframe.io/docs/1.6.0/components/vive-controls.html)
-    ViveControls, 
+    ViveControls as "vive-controls", 
     button_color: "buttonColor" color::Rgb = color::GREY98,
     button_highlight_color: "buttonHighlightColor" color::Rgb = color::Rgb::new(34, 209, 238),
     hand: "hand" Hand = Hand::Left,
@@ -878,7 +1554,7 @@ component_struct!
 component_struct!
 (
     /// [vive-focus-controls](https://aframe.io/docs/1.6.0/components/vive-focus-controls.html)
-    ViveFocusControls, 
+    ViveFocusControls as "vive-focus-controls", 
     arm_model: "armModel" bool = true,
     button_touched_color: "buttonTouchedColor" color::Rgb = color::GREY47,
     button_highlight_color: "buttonHighlightColor" color::Rgb = color::WHITE,
@@ -889,15 +1565,24 @@ component_struct!
 component_struct!
 (
     /// [vr-mode-ui](https://aframe.io/docs/1.6.0/components/vr-mode-ui.html)
-    VrModeUi, 
+    VrModeUi as "vr-mode-ui", 
     enabled: "enabled" bool = true,
-    enter_vr_button: "enterVRButton" Cow<'static, str> = Cow::Borrowed(""),
-    enter_ar_button: "enterARButton" Cow<'static, str> = Cow::Borrowed("")
+    enter_vr_button: "enterVRButton" Selector = Selector::Raw(Cow::Borrowed("")),
+    enter_ar_button: "enterARButton" Selector = Selector::Raw(Cow::Borrowed(""))
 );
+impl VrModeUi
+{
+    /// Replaces Aframe's default enter VR and enter AR buttons with the 
+    /// elements matched by `enter_vr` and `enter_ar`.
+    pub fn with_custom_buttons(enter_vr: Selector, enter_ar: Selector) -> Self
+    {
+        Self { enter_vr_button: enter_vr, enter_ar_button: enter_ar, ..Self::DEFAULT }
+    }
+}
 component_struct!
 (
     /// [wasd-controls](https://aframe.io/docs/1.6.0/components/wasd-controls.html)
-    WasdControls, 
+    WasdControls as "wasd-controls", 
     acceleration: "acceleration" u32 = 65,
     ad_axis: "adAxis" WasdAxis = WasdAxis::X,
     ad_inverted: "adInverted" bool = false,
@@ -917,11 +1602,11 @@ simple_enum!
 component_struct!
 (
     /// [webxr](https://aframe.io/docs/1.6.0/components/webxr.html)
-    WebXR, 
+    WebXR as "webxr", 
     reference_space_type: "referenceSpaceType" ReferenceSpaceType = ReferenceSpaceType::LocalFloor,
-    required_features: "requiredFeatures" List<Cow<'static, str>> = List::DEFAULT,
-    optional_features: "optionalFeatures"  List<Cow<'static, str>> = List::DEFAULT,
-    overlay_element: "overlayElement" Cow<'static, str> = Cow::Borrowed("null")
+    required_features: "requiredFeatures" List<XrFeature> = List::DEFAULT,
+    optional_features: "optionalFeatures" List<XrFeature> = List::DEFAULT,
+    overlay_element: "overlayElement" Selector = Selector::Raw(Cow::Borrowed("null"))
 );
 simple_enum!
 {
@@ -933,10 +1618,56 @@ simple_enum!
     BoundedFloor => "bounded-floor",
     Unbounded => "unbounded"
 }
+/// [webxr#properties_requiredfeatures](https://aframe.io/docs/1.6.0/components/webxr.html#properties_requiredfeatures)
+/// 
+/// A feature to request when creating a WebXR session. Use `Raw` for features
+/// not yet covered here.
+#[derive(Clone, PartialEq, Debug)]
+pub enum XrFeature
+{
+    HandTracking,
+    HitTest,
+    DomOverlay,
+    Anchors,
+    DepthSensing,
+    Layers,
+    Local,
+    LocalFloor,
+    BoundedFloor,
+    Unbounded,
+    Raw(Cow<'static, str>)
+}
+impl std::fmt::Display for XrFeature
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        match self
+        {
+            Self::HandTracking => write!(f, "hand-tracking"),
+            Self::HitTest => write!(f, "hit-test"),
+            Self::DomOverlay => write!(f, "dom-overlay"),
+            Self::Anchors => write!(f, "anchors"),
+            Self::DepthSensing => write!(f, "depth-sensing"),
+            Self::Layers => write!(f, "layers"),
+            Self::Local => write!(f, "local"),
+            Self::LocalFloor => write!(f, "local-floor"),
+            Self::BoundedFloor => write!(f, "bounded-floor"),
+            Self::Unbounded => write!(f, "unbounded"),
+            Self::Raw(feature) => write!(f, "{}", feature)
+        }
+    }
+}
+impl serde::Serialize for XrFeature
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
 component_struct!
 (
     /// [windows-motion-controls](https://aframe.io/docs/1.6.0/components/windows-motion-controls.html)
-    WindowsMotionControls, 
+    WindowsMotionControls as "windows-motion-controls", 
     hand: "hand" Hand = Hand::Left,
     pair: "pair" u32 = 0,
     model: "model" bool = true,
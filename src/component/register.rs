@@ -15,7 +15,7 @@ use wasm_bindgen::{JsCast, prelude::*};
 /// | field | syntax explanation | description |
 /// |-------|--------------------|-------------|
 /// | dependencies | strings separated by commas | names of components that must be initialized prior to this one |
-/// | schema | A hashmap containing string keys and ComponentProperty values. Recommend the maplit crate | Describes component properties |
+/// | schema | A hashmap containing string keys and ComponentProperty values, or an inline `{ name: constructor(default), ... }` block (see `schema!`) | Describes component properties |
 /// | multiple | boolean value | True to allow multiple components on a single entity |
 /// | init | JsValue created from a js_sys::Function() | Called on initialization |
 /// | update | JsValue created from a js_sys::Function(oldData) | Called whenever the component’s properties change |
@@ -29,7 +29,8 @@ use wasm_bindgen::{JsCast, prelude::*};
 /// All parameteres are optional, although the order must be exactly as shown. 
 /// `dependencies` should be a comma-separated list of strings followed by a 
 /// semicolon. `schema` should be a HashMap with string keys and `AframeProperty` 
-/// values. `multiple` is a boolean value. The rest are strings containing 
+/// values, or a braced list of `name: constructor(default)` entries as 
+/// accepted by the `schema!` macro. `multiple` is a boolean value. The rest are strings containing 
 /// javascript code. A `js!` macro is provided to allow inline javascript code 
 /// to be included in the Rust code (See the docs for the `js!` macro for 
 /// caveats and limitations). Here's an example:
@@ -38,11 +39,11 @@ use wasm_bindgen::{JsCast, prelude::*};
 /// let some_component = component_def!
 /// (
 ///     dependencies: "dependency1", "dependency2", some_string,
-///     schema: hashmap!
+///     schema: 
 ///     {
-///         "position" => AframeProperty::float("number", None),
-///         "text" => AframeProperty::string("string", Some(Cow::Borrowed("x"))),
-///         "autoplay" => AframeProperty::boolean("boolean", Some(true))
+///         "position": number(),
+///         "text": string("x"),
+///         "autoplay": boolean(true)
 ///     },
 ///     multiple: true,
 ///     init: js!
@@ -70,12 +71,25 @@ use wasm_bindgen::{JsCast, prelude::*};
 /// );
 /// unsafe
 /// {
-///     some_component.register("component_name");
+///     some_component.register("component_name")?;
 /// }
 /// ```
 #[macro_export]
 macro_rules! component_def
 {
+    (
+        $(dependencies: $($deps:expr),*;)? 
+        schema: { $($schema:tt)* },
+        $($rest:tt)*
+    ) => 
+    {
+        $crate::component_def!
+        (
+            $(dependencies: $($deps),*;)? 
+            schema: $crate::schema!($($schema)*),
+            $($rest)*
+        )
+    };
     (
         $(dependencies: $($deps:expr),*;)? 
         $(schema: $schema:expr,)?
@@ -139,7 +153,7 @@ macro_rules! component_def
 /// };
 /// unsafe
 /// {
-///     newbox.register("newbox");
+///     newbox.register("newbox")?;
 /// }
 /// ```
 #[macro_export]
@@ -165,6 +179,7 @@ pub struct ComponentReg
 {
     pub schema: HashMap<&'static str, AframeProperty>,
     pub dependencies: Cow<'static, [Cow<'static, str>]>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub multiple: bool,
     // TODO: events: HashMap<Cow<'static, str>, Function(event)>
     #[serde(skip)] pub init: JsValue,
@@ -201,7 +216,7 @@ impl From<&ComponentReg> for JsValue
 {
     fn from(cmr: &ComponentReg) -> Self 
     {
-        let js_value = serde_wasm_bindgen::to_value(cmr).expect("Failed to convert ComponentReg into JsObject");
+        let js_value = to_js_object(cmr).expect("Failed to convert ComponentReg into JsObject");
         define_property(js_value.unchecked_ref(), "init", (cmr.init).unchecked_ref());
         define_property(js_value.unchecked_ref(), "update", (cmr.update).unchecked_ref());
         define_property(js_value.unchecked_ref(), "tick", (cmr.tick).unchecked_ref());
@@ -215,10 +230,25 @@ impl From<&ComponentReg> for JsValue
 }
 impl ComponentReg
 {
-    /// Register a component in aframe. Warning: Aframe must be initialized before this is called.
-    pub unsafe fn register(self, name: &str)
+    /// Register a component in aframe. 
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    pub unsafe fn register(self, name: &str) -> Result<(), RegError>
     {
-        registerComponent(name, (&self).into());
+        registerComponent(name, (&self).into())?;
+        Ok(())
+    }
+
+    /// Register several components in aframe, in order. Registration continues
+    /// past failures, and every failure is returned along with the name of 
+    /// the component that failed to register.
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    pub unsafe fn register_all(components: &[(&str, ComponentReg)]) -> Result<(), Vec<(String, RegError)>>
+    {
+        register_each(components, |name, cmr| unsafe { cmr.clone().register(name) })
     }
 }
 
@@ -244,17 +274,32 @@ impl From<&GeometryReg> for JsValue
 {
     fn from(cmr: &GeometryReg) -> Self 
     {
-        let js_value: JsValue = serde_wasm_bindgen::to_value(cmr).expect("Failed to convert GeometryReg into JsObject");
+        let js_value: JsValue = to_js_object(cmr).expect("Failed to convert GeometryReg into JsObject");
         define_property(js_value.unchecked_ref(), "init", (cmr.init).unchecked_ref());
         js_value
     }
 }
 impl GeometryReg
 {
-    /// Register a custom geometry in aframe. Warning: Aframe must be initialized before this is called.
-    pub unsafe fn register(self, name: &str)
+    /// Register a custom geometry in aframe. 
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    pub unsafe fn register(self, name: &str) -> Result<(), RegError>
+    {
+        registerGeometry(name, (&self).into())?;
+        Ok(())
+    }
+
+    /// Register several custom geometries in aframe, in order. Registration 
+    /// continues past failures, and every failure is returned along with the 
+    /// name of the geometry that failed to register.
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    pub unsafe fn register_all(geometries: &[(&str, GeometryReg)]) -> Result<(), Vec<(String, RegError)>>
     {
-        registerGeometry(name, (&self).into());
+        register_each(geometries, |name, geometry| unsafe { geometry.clone().register(name) })
     }
 }
 
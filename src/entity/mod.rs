@@ -3,7 +3,11 @@
 pub mod primitive;
 
 use std::borrow::Cow;
-use crate::{Attribute, ComponentVec, component::Component};
+use crate::{Attribute, ComponentPreset, ComponentVec, InvalidAttrName, Vector3, component::{Component, ComponentAttrCache, Material, Position, Visible}, utils::{AttributeMap, ComponentMap, ConstDefault, htmlify, owned_attrs, owned_str}};
+#[cfg(feature = "web")]
+use wasm_bindgen::{JsCast, JsValue};
+#[cfg(feature = "web")]
+use js_sys::{Function, Reflect};
 
 /// Defines the high-level API for describing entities, with one form for 
 /// describing general entities and another for defining specific primitives.
@@ -43,7 +47,7 @@ use crate::{Attribute, ComponentVec, component::Component};
 ///         component::Animation,
 ///         property: Cow::Borrowed("light.intensity"),
 ///         to: Cow::Borrowed("1.0"),
-///         start_events: component::List(Cow::Borrowed(&[Cow::Borrowed("mouseenter")])),
+///         start_events: ["mouseenter"].into(),
 ///         dur: 250
 ///     }),
 ///     ("animation__mouseleave", component!
@@ -51,7 +55,7 @@ use crate::{Attribute, ComponentVec, component::Component};
 ///         component::Animation,
 ///         property: Cow::Borrowed("light.intensity"),
 ///         to: Cow::Borrowed("0.0"),
-///         start_events: component::List(Cow::Borrowed(&[Cow::Borrowed("mouseleave")])),
+///         start_events: ["mouseleave"].into(),
 ///         dur: 250
 ///     }),
 ///     children: entity!
@@ -77,7 +81,7 @@ macro_rules! entity
 {
     ( 
         $(attributes: $(($attr_id:literal, $attr_value:expr)),*)? $(,)?
-        $(components: $(($cmp_id:literal, $cmp_value:expr)),*)? $(,)? 
+        $(components: $(($cmp_id:expr, $cmp_value:expr)),*)? $(,)? 
         $(children: $($child:expr),*)? 
     ) => 
     {
@@ -100,7 +104,7 @@ macro_rules! entity
     ( 
         primitive: $name:expr,
         $(attributes: $(($attr_id:literal, $attr_value:expr)),*)? $(,)?
-        $(components: $(($cmp_id:literal, $cmp_value:expr)),*)? $(,)? 
+        $(components: $(($cmp_id:expr, $cmp_value:expr)),*)? $(,)? 
         $(children: $($child:expr),*)? 
     ) => 
     {
@@ -140,7 +144,7 @@ macro_rules! attributes_vec
 macro_rules! components_vec
 {
     ( 
-        $(($cmp_id:literal, $cmp_value:expr)),* 
+        $(($cmp_id:expr, $cmp_value:expr)),* 
     ) => 
     {
         vec![ $(($cmp_id.into(), Box::new($cmp_value))),* ]
@@ -154,24 +158,25 @@ pub struct Entity
     primitive: Option<Cow<'static, str>>,
     attributes: Vec<Attribute>,
     components: ComponentVec,
-    children: Vec<Entity>
+    children: Vec<Entity>,
+    component_attrs: ComponentAttrCache
 }
 
 impl Entity
 {
     pub fn new(attributes: Vec<Attribute>, components: Vec<(Cow<'static, str>, Box<dyn Component>)>, children: Vec<Entity>) -> Self
     {
-        Self { primitive: None, attributes, components: ComponentVec(components), children }
+        Self { primitive: None, attributes, components: ComponentVec(components), children, component_attrs: ComponentAttrCache::default() }
     }
 
     pub fn new_primitive(tag: Cow<'static, str>, attributes: Vec<Attribute>, components: Vec<(Cow<'static, str>, Box<dyn Component>)>, children: Vec<Entity>) -> Self
     {
-        Self { primitive: Some(tag), attributes, components: ComponentVec(components), children }
+        Self { primitive: Some(tag), attributes, components: ComponentVec(components), children, component_attrs: ComponentAttrCache::default() }
     }
 
     pub fn with_components(components: Vec<(Cow<'static, str>, Box<dyn Component>)>) -> Self
     {
-        Self { primitive: None, attributes: vec!(), components: ComponentVec(components), children: vec!() }
+        Self { primitive: None, attributes: vec!(), components: ComponentVec(components), children: vec!(), component_attrs: ComponentAttrCache::default() }
     }
 
     pub fn attributes(&self) -> &Vec<Attribute>
@@ -184,6 +189,70 @@ impl Entity
         &mut self.attributes
     }
 
+    /// Get the value of an attribute by name
+    pub fn get_attr(&self, name: &str) -> Option<&str>
+    {
+        htmlify::get_attr(&self.attributes, name)
+    }
+
+    /// Set an attribute, replacing the existing value if there is one. Any 
+    /// duplicate attributes with the same name are removed. Returns the 
+    /// replaced value.
+    pub fn set_attr(&mut self, name: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) -> Option<Cow<'static, str>>
+    {
+        htmlify::set_attr(&mut self.attributes, Attribute::new(name, value))
+    }
+
+    /// Add a mixin to this entity by its id, appending it to the 
+    /// space-separated `mixin` attribute. Mixins applied later take precedence.
+    /// Adding a mixin which is already present does nothing.
+    pub fn add_mixin(&mut self, id: impl Into<Cow<'static, str>>) -> &mut Self
+    {
+        self.add_to_list_attr("mixin", id.into())
+    }
+
+    /// Add a class to this entity, appending it to the space-separated `class`
+    /// attribute so that it can be targeted with [`Selector::class`]. Adding a
+    /// class which is already present does nothing.
+    /// 
+    /// [`Selector::class`]: crate::Selector::class
+    pub fn add_class(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self
+    {
+        self.add_to_list_attr("class", name.into())
+    }
+
+    /// Appends `item` to a space-separated list attribute unless it's already 
+    /// present
+    fn add_to_list_attr(&mut self, attr: &'static str, item: Cow<'static, str>) -> &mut Self
+    {
+        let items = match self.get_attr(attr)
+        {
+            Some(items) if items.split_whitespace().any(|existing| existing == item) => return self,
+            Some(items) if !items.trim().is_empty() => format!("{} {}", items.trim(), item).into(),
+            _ => item
+        };
+        self.set_attr(attr, items);
+        self
+    }
+
+    /// Set a `data-*` attribute. `key` may be given with or without the 
+    /// `data-` prefix, and must otherwise consist of lowercase ASCII letters, 
+    /// digits, `-`, `_` and `.`. Returns the replaced value.
+    pub fn data_attr(&mut self, key: &str, value: impl Into<Cow<'static, str>>) -> Result<Option<Cow<'static, str>>, InvalidAttrName>
+    {
+        let name = htmlify::prefixed_attr_name("data-", key)?;
+        Ok(self.set_attr(name, value))
+    }
+
+    /// Set an `aria-*` attribute. `key` may be given with or without the 
+    /// `aria-` prefix, and must otherwise consist of lowercase ASCII letters, 
+    /// digits, `-`, `_` and `.`. Returns the replaced value.
+    pub fn aria_attr(&mut self, key: &str, value: impl Into<Cow<'static, str>>) -> Result<Option<Cow<'static, str>>, InvalidAttrName>
+    {
+        let name = htmlify::prefixed_attr_name("aria-", key)?;
+        Ok(self.set_attr(name, value))
+    }
+
     pub fn components(&self) -> &Vec<(Cow<'static, str>, Box<dyn Component>)>
     {
         &self.components
@@ -191,9 +260,184 @@ impl Entity
 
     pub fn components_mut(&mut self) -> &mut Vec<(Cow<'static, str>, Box<dyn Component>)>
     {
+        self.component_attrs.invalidate();
         &mut self.components
     }
 
+    /// Consumes this entity, returning it with the given attribute appended
+    pub fn with_attribute(mut self, name: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) -> Self
+    {
+        self.attributes.push(Attribute::new(name, value));
+        self
+    }
+
+    /// Consumes this entity, returning it with the given component appended
+    pub fn with_component(mut self, name: impl Into<Cow<'static, str>>, cmp: impl Component + 'static) -> Self
+    {
+        self.components_mut().push((name.into(), Box::new(cmp)));
+        self
+    }
+
+    /// Moves attributes named after a built-in component (e.g. `position` or 
+    /// `light`) into the entity's components, here and in every descendant. 
+    /// See [`Scene::promote_attributes`](crate::Scene::promote_attributes).
+    pub fn promote_attributes(&mut self) -> &mut Self
+    {
+        let promoted = crate::utils::parse::promote_attributes(&mut self.attributes);
+        if !promoted.is_empty()
+        {
+            self.components_mut().extend(promoted);
+        }
+        for child in self.children.iter_mut()
+        {
+            child.promote_attributes();
+        }
+        self
+    }
+
+    /// Adds each component of `preset` which this entity doesn't already have.
+    /// Components already on the entity are left untouched.
+    pub fn apply_preset(&mut self, preset: &ComponentPreset) -> &mut Self
+    {
+        let missing: Vec<_> = preset.components().iter()
+            .filter(|(name, _)| !self.components.iter().any(|(existing, _)| existing == name))
+            .cloned()
+            .collect();
+        if !missing.is_empty()
+        {
+            self.components_mut().extend(missing);
+        }
+        self
+    }
+
+    /// Find a component by name, downcast to its concrete type.
+    fn component_mut<T: Component>(&mut self, name: &str) -> Option<&mut T>
+    {
+        self.components_mut()
+            .iter_mut()
+            .filter(|(cmp_name, _)| cmp_name == name)
+            .find_map(|(_, cmp)| (&mut **cmp as &mut dyn std::any::Any).downcast_mut::<T>())
+    }
+
+    /// Show or hide this entity by updating its `visible` component, adding 
+    /// one if it doesn't have one yet.
+    pub fn set_visible(&mut self, visible: bool)
+    {
+        match self.component_mut::<Visible>("visible")
+        {
+            Some(component) => component.visible = visible,
+            None => self.components_mut().push((Cow::Borrowed("visible"), Box::new(Visible { visible })))
+        }
+    }
+
+    /// Turn wireframe rendering of this entity's material on or off, which is
+    /// useful for debugging geometry. A default `material` component is added
+    /// when enabling wireframe on an entity without one. Disabling wireframe on
+    /// an entity without a material does nothing.
+    pub fn wireframe(&mut self, enabled: bool)
+    {
+        match self.component_mut::<Material>("material")
+        {
+            Some(material) => material.wireframe = enabled,
+            None if enabled => 
+            {
+                let material = Material { wireframe: true, ..Material::DEFAULT };
+                self.components_mut().push((Cow::Borrowed("material"), Box::new(material)));
+            },
+            None => ()
+        }
+    }
+
+    /// Show or hide the live element with the given id by setting its 
+    /// `visible` attribute.
+    #[cfg(feature = "web")]
+    pub fn set_visible_in_dom(id: &str, visible: bool) -> Result<(), JsValue>
+    {
+        web_sys::window()
+            .and_then(|win| win.document())
+            .and_then(|document| document.get_element_by_id(id))
+            .ok_or_else(|| JsValue::from_str(&format!("No element with id: {}", id)))?
+            .set_attribute("visible", if visible { "true" } else { "false" })
+    }
+
+    /// Converts every borrowed string held by this entity and its descendants
+    /// (the primitive tag, attributes and component names) into an owned 
+    /// string, e.g. before caching a scene built from borrowed configuration.
+    /// Strings which are already owned are moved rather than copied. Component
+    /// values are left as they are.
+    pub fn into_owned(self) -> Self
+    {
+        Self
+        {
+            primitive: self.primitive.map(owned_str),
+            attributes: owned_attrs(self.attributes),
+            components: self.components.into_owned(),
+            children: self.children.into_iter().map(Entity::into_owned).collect(),
+            component_attrs: ComponentAttrCache::default()
+        }
+    }
+
+    /// Sets the given components on the live element with the given id, 
+    /// looking the element up once for the whole batch. Components with more 
+    /// than one property are passed to Aframe's `setAttribute` in object form,
+    /// which spares Aframe from parsing a property string; components with a 
+    /// single property are passed as strings. Returns an error if the element
+    /// does not exist.
+    #[cfg(feature = "web")]
+    pub fn apply_to_dom(id: &str, components: &[(Cow<'static, str>, Box<dyn Component>)]) -> Result<(), JsValue>
+    {
+        let element = web_sys::window()
+            .and_then(|win| win.document())
+            .and_then(|document| document.get_element_by_id(id))
+            .ok_or_else(|| JsValue::from_str(&format!("No element with id: {}", id)))?;
+        let set_attribute: Function = Reflect::get(&element, &JsValue::from_str("setAttribute"))?.dyn_into()?;
+        for (name, cmp) in components
+        {
+            let props = cmp.as_map();
+            let value = if props.len() > 1 
+            { 
+                crate::utils::to_js_object(&props)? 
+            } 
+            else 
+            { 
+                JsValue::from_str(&cmp.to_string()) 
+            };
+            set_attribute.call2(&element, &JsValue::from_str(name), &value)?;
+        }
+        Ok(())
+    }
+
+    /// The components which differ between this entity and `other`, for 
+    /// updating a live element to match `other` with as few DOM changes as 
+    /// possible. Each changed or added component is returned with its new 
+    /// serialized value (suitable for `setAttribute`), and each removed 
+    /// component with `None` (see 
+    /// [`remove_component`](crate::sys::remove_component)). Attributes and 
+    /// children are not compared.
+    pub fn diff(&self, other: &Entity) -> Vec<(Cow<'static, str>, Option<String>)>
+    {
+        fn find<'a>(entity: &'a Entity, name: &str) -> Option<&'a dyn Component>
+        {
+            entity.components.iter()
+                .find(|(cmp_name, _)| cmp_name == name)
+                .map(|(_, cmp)| cmp.as_ref())
+        }
+        let changed = other.components.iter()
+            .filter(|(name, cmp)| !find(self, name).is_some_and(|old| old.eq(cmp.as_ref())))
+            .map(|(name, cmp)| (name.clone(), Some(cmp.to_string())));
+        let removed = self.components.iter()
+            .filter(|(name, _)| find(other, name).is_none())
+            .map(|(name, _)| (name.clone(), None));
+        changed.chain(removed).collect()
+    }
+
+    /// The components of this entity as attributes. These are cached after the
+    /// first call until the components are next accessed mutably.
+    pub(crate) fn component_attributes(&self) -> &Vec<Attribute>
+    {
+        self.component_attrs.get(&self.components)
+    }
+
     pub fn children(&self) -> &Vec<Entity>
     {
         &self.children
@@ -204,12 +448,115 @@ impl Entity
         &mut self.children
     }
 
+    /// Appends the given entities to the children of this entity
+    pub fn add_children(&mut self, children: impl IntoIterator<Item = Entity>) -> &mut Self
+    {
+        self.children.extend(children);
+        self
+    }
+
+    /// The number of entities nested beneath this entity, not counting this
+    /// entity itself
+    pub fn descendant_count(&self) -> usize
+    {
+        self.children.iter().map(|child| 1 + child.descendant_count()).sum()
+    }
+
+    /// The number of levels in this entity's tree. An entity with no children
+    /// has a depth of 1.
+    pub fn max_depth(&self) -> usize
+    {
+        1 + self.children.iter().map(Entity::max_depth).max().unwrap_or(0)
+    }
+
+    /// Find the first entity in this tree (including this entity) whose `id` 
+    /// attribute is `id`, searching in pre-order.
+    pub fn find(&self, id: &str) -> Option<&Entity>
+    {
+        if self.get_attr("id") == Some(id)
+        {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+
+    /// Mutable version of [`Entity::find`]
+    pub fn find_mut(&mut self, id: &str) -> Option<&mut Entity>
+    {
+        if self.get_attr("id") == Some(id)
+        {
+            return Some(self);
+        }
+        self.children.iter_mut().find_map(|child| child.find_mut(id))
+    }
+
     pub fn tag(&self) -> Cow<'static, str>
     {
         match self.primitive
         {
             Some(ref tag) => tag.clone(),
-            None => self.tag().into()
+            None => Cow::Borrowed("a-entity")
         }
     }
 }
+
+/// Serializes an entity as an object with its `tag`, an `attributes` map, a 
+/// `components` map of component names to their properties, and an array of
+/// `children`.
+impl serde::Serialize for Entity
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        use serde::ser::SerializeStruct;
+        let mut entity = serializer.serialize_struct("Entity", 4)?;
+        entity.serialize_field("tag", &self.tag())?;
+        entity.serialize_field("attributes", &AttributeMap(&self.attributes))?;
+        entity.serialize_field("components", &ComponentMap(&self.components.0))?;
+        entity.serialize_field("children", &self.children)?;
+        entity.end()
+    }
+}
+
+/// Generates a grid of entities, `cols` wide and `rows` deep. The entity for 
+/// each cell is created by `make`, which receives the column and row indices. 
+/// Each entity is then offset by `spacing`: columns advance along the x axis by
+/// `spacing.x`, while rows advance along the y and z axes by `spacing.y` and 
+/// `spacing.z`. This means a floor can be tiled with a spacing of `(1, 0, 1)` 
+/// and a wall with a spacing of `(1, 1, 0)`. If the entity already has a 
+/// `position` component, the offset is added to it, otherwise a `position` 
+/// component is added.
+/// ```ignore
+/// let floor = entity::grid(10, 10, Vector3 { x: 1.0, y: 0.0, z: 1.0 }, |col, row| entity!
+/// {
+///     primitive: primitive::A_PLANE,
+///     attributes: ("id", format!("tile-{}-{}", col, row)),
+///     components: ("rotation", component!(component::Rotation, x: -90.0))
+/// });
+/// ```
+pub fn grid(cols: usize, rows: usize, spacing: Vector3, make: impl Fn(usize, usize) -> Entity) -> Vec<Entity>
+{
+    (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| (col, row)))
+        .map(|(col, row)| 
+        {
+            let mut entity = make(col, row);
+            let (x, y, z) = 
+            (
+                (col as f64 * spacing.x) as f32, 
+                (row as f64 * spacing.y) as f32, 
+                (row as f64 * spacing.z) as f32
+            );
+            match entity.component_mut::<Position>("position")
+            {
+                Some(position) => 
+                {
+                    position.x += x;
+                    position.y += y;
+                    position.z += z;
+                },
+                None => entity.components_mut().push((Cow::Borrowed("position"), Box::new(Position { x, y, z })))
+            }
+            entity
+        })
+        .collect()
+}
@@ -1,7 +1,12 @@
 //! Module for the registration of primitives and for constants that map to
 //! Aframe's built-in primitives.
 
-use crate::sys::registerPrimitive;
+#[cfg(feature = "web")]
+use crate::{sys::registerPrimitive, utils::{RegError, register_each, to_js_object}};
+#[cfg(feature = "web")]
+use js_sys::{Array, Function, Object, Reflect};
+#[cfg(feature = "web")]
+use wasm_bindgen::{JsCast, JsValue};
 use std::collections::HashMap;
 
 use super::*;
@@ -76,7 +81,7 @@ pub const A_VIDEOSPHERE: &'static str = "a-videosphere";
 ///         property: Cow::Borrowed("rotation"),
 ///         from: Cow::Borrowed("0 45 0"),
 ///         to: Cow::Borrowed("0 405 0"),
-///         start_events: component::List(Cow::Borrowed(&[Cow::Borrowed("click")])),
+///         start_events: ["click"].into(),
 ///         dur: 900,
 ///         easing: component::Easing::EaseOutCubic
 ///     }),
@@ -84,7 +89,7 @@ pub const A_VIDEOSPHERE: &'static str = "a-videosphere";
 ///     ("material", component!
 ///     {
 ///         component::Material, 
-///         props: component::MaterialProps(Cow::Owned(vec!((Cow::Borrowed("src"), Cow::Borrowed("#ramen")))))
+///         src: Selector::id("ramen").into()
 ///     })
 ///     mappings: 
 ///     ("src", "material.src"), 
@@ -142,10 +147,126 @@ impl PrimitiveReg
         Self { default_components, mappings }
     }
 
-    /// Register a primitive in aframe. Warning: Aframe must be initialized before this is called.
-    pub unsafe fn register(&self, name: &str) -> Result<(), serde_wasm_bindgen::Error>
+    /// Register a primitive in aframe. 
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    #[cfg(feature = "web")]
+    pub unsafe fn register(&self, name: &str) -> Result<(), RegError>
     {
-        registerPrimitive(name, serde_wasm_bindgen::to_value(self)?);
+        registerPrimitive(name, to_js_object(self)?)?;
         Ok(())
     }
+
+    /// Register several primitives in aframe, in order. Registration continues
+    /// past failures, and every failure is returned along with the name of 
+    /// the primitive that failed to register.
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    #[cfg(feature = "web")]
+    pub unsafe fn register_all(primitives: &[(&str, PrimitiveReg)]) -> Result<(), Vec<(String, RegError)>>
+    {
+        register_each(primitives, |name, primitive| unsafe { primitive.register(name) })
+    }
+}
+
+
+/// A full custom element definition, registered via `customElements.define`.
+/// 
+/// Where a [PrimitiveReg] only declares an entity tag in terms of default 
+/// components and attribute mappings, this registers an element class of its
+/// own. This gives access to the element lifecycle (creation, connection, 
+/// disconnection and attribute changes), which is necessary for elements that
+/// need to run logic outside of any component. All JsValues should be derived
+/// from [`js_sys::Function`].
+/// ```ignore
+/// let element = PrimitiveElementReg
+/// {
+///     connected: js!(console.log("connected");).into(),
+///     disconnected: js!(console.log("disconnected");).into(),
+///     ..PrimitiveElementReg::default()
+/// };
+/// unsafe
+/// {
+///     element.register_element("a-lifecycle")?;
+/// }
+/// ```
+#[cfg(feature = "web")]
+#[derive(Clone)]
+pub struct PrimitiveElementReg
+{
+    /// Called from the element's constructor when it is created
+    pub created: JsValue,
+    /// Called when the element is inserted into the document (`connectedCallback`)
+    pub connected: JsValue,
+    /// Called when the element is removed from the document (`disconnectedCallback`)
+    pub disconnected: JsValue,
+    /// Called with (attrName, oldVal, newVal) whenever one of the 
+    /// `observed_attributes` changes (`attributeChangedCallback`)
+    pub attribute_changed: JsValue,
+    /// Names of the attributes whose changes are passed to `attribute_changed`
+    pub observed_attributes: Vec<&'static str>,
+    /// Additional functions or data to define on the element prototype
+    pub properties: HashMap<&'static str, JsValue>
+}
+#[cfg(feature = "web")]
+impl Default for PrimitiveElementReg
+{
+    fn default() -> Self 
+    {
+        let empty_fn: JsValue = js_sys::Function::default().into();
+        Self
+        {
+            created: empty_fn.clone(),
+            connected: empty_fn.clone(),
+            disconnected: empty_fn.clone(),
+            attribute_changed: empty_fn,
+            observed_attributes: vec!(),
+            properties: HashMap::new()
+        }
+    }
+}
+#[cfg(feature = "web")]
+impl PrimitiveElementReg
+{
+    /// Register a custom element with `customElements.define`. 
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    pub unsafe fn register_element(&self, name: &str) -> Result<(), RegError>
+    {
+        let registry = Reflect::get(&js_sys::global(), &JsValue::from_str("customElements"))?;
+        let define: Function = Reflect::get(&registry, &JsValue::from_str("define"))?.dyn_into()?;
+        define.call2(&registry, &JsValue::from_str(name), &self.definition()?)?;
+        Ok(())
+    }
+
+    /// Builds a class extending `HTMLElement` whose constructor calls `created`
+    fn definition(&self) -> Result<JsValue, JsValue>
+    {
+        let class = Function::new_with_args
+        (
+            "created", 
+            "return class extends HTMLElement { constructor() { super(); created.call(this); } };"
+        ).call1(&JsValue::NULL, &self.created)?;
+        let descriptors = Object::new();
+        let callbacks = 
+        [
+            ("connectedCallback", &self.connected),
+            ("disconnectedCallback", &self.disconnected),
+            ("attributeChangedCallback", &self.attribute_changed)
+        ];
+        for (name, value) in callbacks.into_iter().chain(self.properties.iter().map(|(k, v)| (*k, v)))
+        {
+            let descriptor = Object::new();
+            Reflect::set(&descriptor, &JsValue::from_str("value"), value)?;
+            Reflect::set(&descriptors, &JsValue::from_str(name), &descriptor)?;
+        }
+        let prototype: Object = Reflect::get(&class, &JsValue::from_str("prototype"))?.dyn_into()?;
+        Object::define_properties(&prototype, &descriptors);
+        let observed: Array = self.observed_attributes.iter().map(|name| JsValue::from_str(name)).collect();
+        Reflect::set(&class, &JsValue::from_str("observedAttributes"), &observed)?;
+        Ok(class)
+    }
 }
@@ -3,6 +3,7 @@
 #[cfg(test)]
 pub mod tests;
 
+#[cfg(feature = "web")]
 pub mod sys;
 pub mod shader;
 pub mod component;
@@ -10,9 +11,14 @@ pub mod utils;
 pub mod entity;
 pub mod scene;
 pub mod assets;
+#[cfg(feature = "web")]
 pub mod system;
+#[cfg(feature = "web")]
+pub mod registry;
 #[cfg(feature = "yew-support")]
 pub mod yew_ext;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 pub use shader::*;
 pub use component::*;
@@ -20,8 +26,13 @@ pub use utils::*;
 pub use entity::*;
 pub use scene::*;
 pub use assets::*;
+#[cfg(feature = "web")]
 pub use system::*;
-#[cfg(feature = "yew-support")]
+#[cfg(feature = "web")]
+pub use registry::*;
+
+/// URL of the Aframe script loaded by `init_aframe`. Current Aframe version: 1.6.0
+pub const AFRAME_URL: &str = "https://aframe.io/releases/1.6.0/aframe.min.js";
 
 /// Async function which initializes aframe by adding the aframe script tag
 /// to the document header and waiting for the script onload event. 
@@ -29,8 +40,6 @@ pub use system::*;
 #[cfg(feature = "init")]
 pub async fn init_aframe() -> Result<(), InitError>
 {
-    const LINK: &'static str = "https://aframe.io/releases/1.6.0/aframe.min.js";
-    
     use wasm_bindgen::prelude::*;
     use std::sync::{Arc, Mutex};
     use async_lock::Barrier;
@@ -39,9 +48,6 @@ pub async fn init_aframe() -> Result<(), InitError>
     let result: Arc<Mutex<Result<(), InitError>>> = Arc::new(Mutex::new(Err(InitError)));
     let barrier = Arc::new(Barrier::new(2));
 
-    let result_outer = result.clone();
-    let barrier_inner = barrier.clone();
-
     // Append Aframe to document
     let document = web_sys::window()
         .and_then(|win| win.document())
@@ -54,22 +60,33 @@ pub async fn init_aframe() -> Result<(), InitError>
         .map_err(|_| InitError)?;
     head.append_child(&script_element)
         .map_err(|_| InitError)?;
-    let closure = 
+    // Records the outcome and releases the barrier. Only one of the load and 
+    // error handlers will ever run.
+    let handler = |outcome: Result<(), InitError>|
     {
+        let result = result.clone();
+        let barrier = barrier.clone();
         Closure::once(Box::new(move || 
         {
-            *result.lock().unwrap() = Ok(());
+            *result.lock().unwrap() = outcome;
             drop(result);
-            block_on(barrier_inner.wait());
+            block_on(barrier.wait());
         }) as Box<dyn FnOnce()>)
     };
-    script_element.set_onload(Some(closure.as_ref().unchecked_ref()));
-    closure.forget();
-    script_element.set_attribute("src", LINK)
+    let onload = handler(Ok(()));
+    let onerror = handler(Err(InitError));
+    script_element.set_onload(Some(onload.as_ref().unchecked_ref()));
+    script_element.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+    script_element.set_attribute("src", AFRAME_URL)
         .map_err(|_| InitError)?;
 
     barrier.wait().await;
-    Arc::try_unwrap(result_outer)
+    // Detach the handlers before freeing them. Dropping them also releases 
+    // their references to `result`.
+    script_element.set_onload(None);
+    script_element.set_onerror(None);
+    drop((onload, onerror));
+    Arc::try_unwrap(result)
         .map_err(|_| InitError)
         .and_then(|mutex| mutex.into_inner().map_err(|_| InitError))
         .and_then(|result| result)
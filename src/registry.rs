@@ -0,0 +1,153 @@
+//! A collection of definitions which can be registered in Aframe together.
+
+use std::borrow::Cow;
+use js_sys::Reflect;
+use wasm_bindgen::JsValue;
+use crate::{component::{ComponentReg, GeometryReg}, entity::primitive::PrimitiveReg, shader::Shader, system::SystemReg, utils::RegError};
+
+/// Collects component, geometry, shader, primitive and system definitions by 
+/// name so that they can all be registered in one place. Adding a definition 
+/// with the same name as an existing definition of the same kind replaces it.
+/// ```ignore
+/// let registry = Registry::new()
+///     .system("my-sys", my_sys)
+///     .component("fps", fps)
+///     .primitive("a-fps", fps_primitive);
+/// let report = unsafe { registry.register_all() };
+/// assert!(report.failed.is_empty());
+/// ```
+#[derive(Default)]
+pub struct Registry
+{
+    components: Vec<(Cow<'static, str>, ComponentReg)>,
+    geometries: Vec<(Cow<'static, str>, GeometryReg)>,
+    shaders: Vec<(Cow<'static, str>, Shader<'static, 'static, 'static>)>,
+    primitives: Vec<(Cow<'static, str>, PrimitiveReg)>,
+    systems: Vec<(Cow<'static, str>, SystemReg)>
+}
+
+/// The outcome of [`Registry::register_all`], listing names by what happened 
+/// to them.
+#[derive(Debug, Default)]
+pub struct RegistryReport
+{
+    /// Definitions which were newly registered
+    pub registered: Vec<String>,
+    /// Definitions which were already registered in Aframe, and were skipped
+    pub skipped: Vec<String>,
+    /// Definitions which failed to register
+    pub failed: Vec<(String, RegError)>
+}
+
+impl Registry
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn component(mut self, name: impl Into<Cow<'static, str>>, definition: ComponentReg) -> Self
+    {
+        insert(&mut self.components, name.into(), definition);
+        self
+    }
+
+    pub fn geometry(mut self, name: impl Into<Cow<'static, str>>, definition: GeometryReg) -> Self
+    {
+        insert(&mut self.geometries, name.into(), definition);
+        self
+    }
+
+    pub fn shader(mut self, name: impl Into<Cow<'static, str>>, definition: Shader<'static, 'static, 'static>) -> Self
+    {
+        insert(&mut self.shaders, name.into(), definition);
+        self
+    }
+
+    pub fn primitive(mut self, name: impl Into<Cow<'static, str>>, definition: PrimitiveReg) -> Self
+    {
+        insert(&mut self.primitives, name.into(), definition);
+        self
+    }
+
+    pub fn system(mut self, name: impl Into<Cow<'static, str>>, definition: SystemReg) -> Self
+    {
+        insert(&mut self.systems, name.into(), definition);
+        self
+    }
+
+    /// Register everything in this registry, skipping anything which has 
+    /// already been registered in Aframe. Shaders, geometries and systems are
+    /// registered first, followed by components and then primitives, so that 
+    /// primitives may use any of the components registered alongside them. 
+    /// Registration continues past failures.
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    pub unsafe fn register_all(&self) -> RegistryReport
+    {
+        let mut report = RegistryReport::default();
+        register_kind(&mut report, &self.shaders, "shaders", |name, shader| unsafe { shader.register(name) });
+        register_kind(&mut report, &self.geometries, "geometries", |name, geometry| unsafe { geometry.clone().register(name) });
+        register_kind(&mut report, &self.systems, "systems", |name, system| unsafe { system.clone().register(name) });
+        register_kind(&mut report, &self.components, "components", |name, component| unsafe { component.clone().register(name) });
+        register_kind(&mut report, &self.primitives, "primitives", |name, primitive| unsafe { primitive.register(name) });
+        report
+    }
+}
+
+/// Insert a named definition, replacing any existing definition with that name
+fn insert<T>(entries: &mut Vec<(Cow<'static, str>, T)>, name: Cow<'static, str>, definition: T)
+{
+    match entries.iter_mut().find(|(existing, _)| *existing == name)
+    {
+        Some((_, existing)) => *existing = definition,
+        None => entries.push((name, definition))
+    }
+}
+
+/// Register each definition of one kind, recording the outcomes in the report
+fn register_kind<T>
+(
+    report: &mut RegistryReport, 
+    entries: &[(Cow<'static, str>, T)], 
+    kind: &str, 
+    mut register: impl FnMut(&str, &T) -> Result<(), RegError>
+)
+{
+    let registered = registered(kind);
+    for (name, definition) in entries
+    {
+        let already_registered = registered.as_ref()
+            .is_some_and(|registered| Reflect::has(registered, &JsValue::from_str(name)).unwrap_or(false));
+        if already_registered
+        {
+            report.skipped.push(name.to_string());
+        }
+        else
+        {
+            match register(name, definition)
+            {
+                Ok(()) => report.registered.push(name.to_string()),
+                Err(err) => report.failed.push((name.to_string(), err))
+            }
+        }
+    }
+}
+
+/// The object in which Aframe stores registrations of the given kind
+fn registered(kind: &str) -> Option<JsValue>
+{
+    let aframe = Reflect::get(&js_sys::global(), &JsValue::from_str("AFRAME")).ok()?;
+    let registered = Reflect::get(&aframe, &JsValue::from_str(kind)).ok()?;
+    // Primitives are stored one level deeper, in `AFRAME.primitives.primitives`
+    let registered = if kind == "primitives"
+    {
+        Reflect::get(&registered, &JsValue::from_str(kind)).ok()?
+    }
+    else
+    {
+        registered
+    };
+    Some(registered).filter(|registered| registered.is_object())
+}
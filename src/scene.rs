@@ -1,7 +1,13 @@
 //! The scene construct, the top-level container for all other Aframe structures.
 
 use std::borrow::Cow;
-use crate::{ComponentVec, Assets, Attribute, component::Component, entity::*};
+use crate::{ComponentVec, Assets, Attribute, Htmlify, component::{Component, Stats}, entity::*, utils::{AttributeMap, ComponentMap, HtmlNode}};
+#[cfg(feature = "web")]
+use js_sys::{Promise, Reflect};
+#[cfg(feature = "web")]
+use wasm_bindgen::JsValue;
+#[cfg(feature = "web")]
+use wasm_bindgen_futures::JsFuture;
 
 /// Provided to define a `Scene` struct.
 /// ```ignore
@@ -30,7 +36,7 @@ macro_rules! scene
     ( 
         $(attributes: $(($attr_id:literal, $attr_value:expr)),*)? $(,)?
         assets: $assets:expr,
-        $(components: $(($cmp_id:literal, $cmp_value:expr)),*)? $(,)? 
+        $(components: $(($cmp_id:expr, $cmp_value:expr)),*)? $(,)? 
         $(children: $($child:expr),*)? 
     ) => 
     {
@@ -74,6 +80,115 @@ impl Scene
         Self { attributes: vec!(), assets: Assets::default(), components: ComponentVec(components), children: vec!() }
     }
 
+    /// Consumes this scene, returning it with the given attribute appended
+    pub fn with_attribute(mut self, name: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) -> Self
+    {
+        self.attributes.push(Attribute::new(name, value));
+        self
+    }
+
+    /// Consumes this scene, returning it with the given component appended
+    pub fn with_component(mut self, name: impl Into<Cow<'static, str>>, cmp: impl Component + 'static) -> Self
+    {
+        self.components.push((name.into(), Box::new(cmp)));
+        self
+    }
+
+    /// Turn on the usual debugging aids: the `stats` component, which shows 
+    /// performance statistics, and the `debug` attribute, which makes component
+    /// changes visible in the DOM.
+    pub fn with_debug(mut self) -> Self
+    {
+        if !self.components.iter().any(|(name, _)| name == "stats")
+        {
+            self.components.push((Cow::Borrowed("stats"), Box::new(Stats {})));
+        }
+        self.set_attr("debug", "");
+        self
+    }
+
+    /// Parses the first `<a-scene>` in the given HTML (which may be a full 
+    /// document or just the scene) into a `Scene`, so that hand-authored scenes
+    /// can be imported. The `position`, `rotation`, `scale` and `visible` 
+    /// components are parsed into their component structs; every other 
+    /// component becomes a [`RawComponent`](crate::component::RawComponent).
+    /// On primitives, attributes which are not built-in components are kept 
+    /// as attributes, since they are usually primitive mappings.
+    pub fn from_html(html: &str) -> Result<Scene, crate::ParseError>
+    {
+        crate::utils::parse::parse_scene(html)
+    }
+
+    /// Renders this scene as a standalone HTML document which loads Aframe from
+    /// `aframe_url` (usually [`AFRAME_URL`](crate::AFRAME_URL)), suitable for 
+    /// static hosting.
+    pub fn to_html_document(&self, aframe_url: &str) -> String
+    {
+        format!
+        (
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8"><script src="{}"></script></head><body>{}</body></html>"#, 
+            aframe_url.replace('"', "&quot;"), 
+            self.as_raw_html()
+        )
+    }
+
+    /// Moves attributes named after a built-in component (e.g. `fog` or 
+    /// `cursor`) into the scene's components, along with those of every entity
+    /// in the scene. This helps migrate hand-written scenes to the typed model.
+    /// Values are parsed the same way as in [`Scene::from_html`], so a few 
+    /// common components become their typed structs and the rest are kept as
+    /// [`RawComponent`](crate::component::RawComponent)s. Other attributes are
+    /// left in place.
+    pub fn promote_attributes(&mut self) -> &mut Self
+    {
+        let promoted = crate::utils::parse::promote_attributes(&mut self.attributes);
+        self.components.extend(promoted);
+        for child in self.children.iter_mut()
+        {
+            child.promote_attributes();
+        }
+        self
+    }
+
+    /// The total number of entities this scene will produce when mounted
+    pub fn entity_count(&self) -> usize
+    {
+        self.children.iter().map(|child| 1 + child.descendant_count()).sum()
+    }
+
+    /// The number of levels of entities nested in this scene, or 0 if the 
+    /// scene has no entities. See [`Entity::max_depth`].
+    pub fn max_depth(&self) -> usize
+    {
+        self.children.iter().map(Entity::max_depth).max().unwrap_or(0)
+    }
+
+    /// Find the first entity in this scene whose `id` attribute is `id`, 
+    /// searching in pre-order.
+    pub fn find(&self, id: &str) -> Option<&Entity>
+    {
+        self.children.iter().find_map(|child| child.find(id))
+    }
+
+    /// Mutable version of [`Scene::find`]
+    pub fn find_mut(&mut self, id: &str) -> Option<&mut Entity>
+    {
+        self.children.iter_mut().find_map(|child| child.find_mut(id))
+    }
+
+    /// Converts every borrowed string held by this scene, its assets and its
+    /// entities into an owned string. See [`Entity::into_owned`].
+    pub fn into_owned(self) -> Self
+    {
+        Self
+        {
+            attributes: crate::utils::owned_attrs(self.attributes),
+            assets: self.assets.into_owned(),
+            components: self.components.into_owned(),
+            children: self.children.into_iter().map(Entity::into_owned).collect()
+        }
+    }
+
     pub fn assets(&self) -> &Assets
     {
         &self.assets
@@ -94,6 +209,20 @@ impl Scene
         &mut self.attributes
     }
 
+    /// Get the value of an attribute by name
+    pub fn get_attr(&self, name: &str) -> Option<&str>
+    {
+        crate::utils::htmlify::get_attr(&self.attributes, name)
+    }
+
+    /// Set an attribute, replacing the existing value if there is one. Any 
+    /// duplicate attributes with the same name are removed. Returns the 
+    /// replaced value.
+    pub fn set_attr(&mut self, name: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) -> Option<Cow<'static, str>>
+    {
+        crate::utils::htmlify::set_attr(&mut self.attributes, Attribute::new(name, value))
+    }
+
     pub fn components(&self) -> &Vec<(Cow<'static, str>, Box<dyn Component>)>
     {
         &self.components
@@ -113,4 +242,58 @@ impl Scene
     {
         &mut self.children
     }
-}
\ No newline at end of file
+
+    /// Appends the given entities to the children of this scene
+    pub fn add_children(&mut self, children: impl IntoIterator<Item = Entity>) -> &mut Self
+    {
+        self.children.extend(children);
+        self
+    }
+
+    /// Waits until the given `<a-scene>` element fires its `loaded` event, at 
+    /// which point assets have been preloaded and systems initialized. Resolves
+    /// immediately if the scene has already loaded.
+    #[cfg(feature = "web")]
+    pub async fn wait_until_loaded(el: &web_sys::Element) -> Result<(), JsValue>
+    {
+        if Reflect::get(el, &JsValue::from_str("hasLoaded"))?.is_truthy()
+        {
+            return Ok(());
+        }
+        let mut listen_result = Ok(());
+        let loaded = Promise::new(&mut |resolve, _reject|
+        {
+            let options = web_sys::AddEventListenerOptions::new();
+            options.set_once(true);
+            listen_result = el.add_event_listener_with_callback_and_add_event_listener_options("loaded", &resolve, &options);
+        });
+        listen_result?;
+        JsFuture::from(loaded).await.map(|_| ())
+    }
+
+    /// Every entity within the given `<a-scene>` element which has the named 
+    /// component, e.g. `Scene::entities_with_component(&scene_el, "light")`.
+    #[cfg(feature = "web")]
+    pub fn entities_with_component(el: &web_sys::Element, name: &str) -> Vec<web_sys::Element>
+    {
+        el.query_selector_all(&format!("[{}]", name))
+            .map(crate::sys::node_list_elements)
+            .unwrap_or_default()
+    }
+}
+/// Serializes a scene in the same shape as an [`Entity`], with its assets as a
+/// generic `assets` element of `tag`, `attributes` and `children`.
+impl serde::Serialize for Scene
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        use serde::ser::SerializeStruct;
+        let mut scene = serializer.serialize_struct("Scene", 5)?;
+        scene.serialize_field("tag", "a-scene")?;
+        scene.serialize_field("attributes", &AttributeMap(&self.attributes))?;
+        scene.serialize_field("components", &ComponentMap(&self.components.0))?;
+        scene.serialize_field("assets", &HtmlNode(&self.assets))?;
+        scene.serialize_field("children", &self.children)?;
+        scene.end()
+    }
+}
@@ -1,12 +1,19 @@
 //! Module to provide tools for shader registration as well as built-in shader 
 //! constants.
 
+// The names of shaders built into Aframe. These are registered by Aframe itself
+// and can be referenced from a material directly, without any registration.
+
+/// Aframe's built-in portal shader, used by the `link` component. To use the 
+/// same uniforms with custom GLSL, register a shader created by [`Shader::portal`]
+/// under a different name.
 pub const PORTAL: &'static str = "portal";
 pub const FLAT: &'static str = "flat";
 pub const STANDARD: &'static str = "standard";
 pub const SDF: &'static str = "sdf";
 pub const MSDF: &'static str = "msdf";
 
+#[cfg(feature = "web")]
 use crate::sys::registerShader;
 use crate::utils::*;
 use std::{borrow::Cow, collections::HashMap};
@@ -61,12 +68,42 @@ impl<'a, 'b, 'c> Shader<'a, 'b, 'c>
         Shader { schema, vertex_shader, fragment_shader }
     }
 
-    /// Register a shader in aframe. Warning: Aframe must be initialized before this is called.
-    pub unsafe fn register(&self, name: &str) -> Result<(), serde_wasm_bindgen::Error>
+    /// Define a shader with the same uniforms as Aframe's built-in portal 
+    /// shader (`borderEnabled`, `backgroundColor`, `pano` and `strokeColor`), 
+    /// using the provided GLSL. Aframe already registers a shader named 
+    /// [`PORTAL`], so this must be registered under another name.
+    pub fn portal(vertex_shader: Cow<'b, str>, fragment_shader: Cow<'c, str>) -> Self
+    {
+        let mut schema = HashMap::new();
+        schema.insert(Cow::Borrowed("borderEnabled"), ShaderProperty::int(IsUniform::Yes, Some(1)));
+        schema.insert(Cow::Borrowed("backgroundColor"), ShaderProperty::color(IsUniform::Yes, Some(color::RED)));
+        schema.insert(Cow::Borrowed("pano"), ShaderProperty::map(IsUniform::Yes, None));
+        schema.insert(Cow::Borrowed("strokeColor"), ShaderProperty::color(IsUniform::Yes, Some(color::WHITE)));
+        Shader { schema, vertex_shader, fragment_shader }
+    }
+
+    /// Register a shader in aframe. 
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    #[cfg(feature = "web")]
+    pub unsafe fn register(&self, name: &str) -> Result<(), RegError>
     {
-        registerShader(name, serde_wasm_bindgen::to_value(self)?);
+        registerShader(name, to_js_object(self)?)?;
         Ok(())
     }
+
+    /// Register several shaders in aframe, in order. Registration continues
+    /// past failures, and every failure is returned along with the name of 
+    /// the shader that failed to register.
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    #[cfg(feature = "web")]
+    pub unsafe fn register_all(shaders: &[(&str, Shader)]) -> Result<(), Vec<(String, RegError)>>
+    {
+        register_each(shaders, |name, shader| unsafe { shader.register(name) })
+    }
 }
 
 /// A property for a shader. This includes the shader type, whether or not this 
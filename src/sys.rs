@@ -5,7 +5,8 @@
 
 use wasm_bindgen::{JsCast, prelude::*};
 use std::convert::TryFrom;
-use js_sys::{Array, Object};
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use wasm_bindgen_futures::JsFuture;
 use std::sync::LazyLock;
 
 static AFRAME: LazyLock<Option<Aframe>> = LazyLock::new(Aframe::get);
@@ -14,28 +15,28 @@ static AFRAME: LazyLock<Option<Aframe>> = LazyLock::new(Aframe::get);
 extern 
 {
     /// [registering-a-primitive](https://aframe.io/docs/1.6.0/introduction/html-and-primitives.html#registering-a-primitive)
-    #[wasm_bindgen(js_namespace = AFRAME)]
-    pub fn registerPrimitive(name: &str, definition: JsValue);
+    #[wasm_bindgen(catch, js_namespace = AFRAME)]
+    pub fn registerPrimitive(name: &str, definition: JsValue) -> Result<(), JsValue>;
 
     /// [register-component-name-definition](https://aframe.io/docs/1.6.0/core/component.html#aframe-registercomponent-name-definition)
-    #[wasm_bindgen(js_namespace = AFRAME)]
-    pub fn registerComponent(name: &str, data: JsValue);
+    #[wasm_bindgen(catch, js_namespace = AFRAME)]
+    pub fn registerComponent(name: &str, data: JsValue) -> Result<(), JsValue>;
 
     /// [registering-a-system](https://aframe.io/docs/1.6.0/core/systems.html#registering-a-system)
-    #[wasm_bindgen(js_namespace = AFRAME)]
-    pub fn registerSystem(name: &str, data: JsValue);
+    #[wasm_bindgen(catch, js_namespace = AFRAME)]
+    pub fn registerSystem(name: &str, data: JsValue) -> Result<(), JsValue>;
 
     /// [register-a-custom-shader-material](https://aframe.io/docs/1.6.0/components/material.html#register-a-custom-shader-material)
-    #[wasm_bindgen(js_namespace = AFRAME)]
-    pub fn registerShader(name: &str, data: JsValue);
+    #[wasm_bindgen(catch, js_namespace = AFRAME)]
+    pub fn registerShader(name: &str, data: JsValue) -> Result<(), JsValue>;
 
     /// [register-a-custom-geometry](https://aframe.io/docs/1.6.0/components/geometry.html#register-a-custom-geometry)
-    #[wasm_bindgen(js_namespace = AFRAME)]
-    pub fn registerGeometry(name: &str, data: JsValue);
+    #[wasm_bindgen(catch, js_namespace = AFRAME)]
+    pub fn registerGeometry(name: &str, data: JsValue) -> Result<(), JsValue>;
 
     /// [aframe_properties_registerelement](https://aframe.io/docs/1.6.0/core/globals.html#aframe_properties_registerelement)
-    #[wasm_bindgen(js_namespace = AFRAME)]
-    pub fn registerElement(name: &str, data: JsValue);
+    #[wasm_bindgen(catch, js_namespace = AFRAME)]
+    pub fn registerElement(name: &str, data: JsValue) -> Result<(), JsValue>;
 
     // /// Checks if a VR headset is connected by looking for orientation data.
     // #[wasm_bindgen(js_namespace = ["AFRAME", "utils", "device"])]
@@ -117,6 +118,422 @@ pub fn version() -> Option<JsValue>
     AFRAME.as_ref().and_then(|aframe| access_field(&aframe.0, "version"))
 }
 
+/// Whether a component with the given name has been registered. Returns false
+/// if Aframe isn't loaded.
+pub fn is_component_registered(name: &str) -> bool
+{
+    is_registered(components(), name)
+}
+
+/// Whether a shader with the given name has been registered. Returns false if
+/// Aframe isn't loaded.
+pub fn is_shader_registered(name: &str) -> bool
+{
+    is_registered(shaders(), name)
+}
+
+/// Whether a primitive with the given tag name (e.g. `"a-box"`) has been 
+/// registered. Returns false if Aframe isn't loaded.
+pub fn is_primitive_registered(name: &str) -> bool
+{
+    is_registered(primitives(), name)
+}
+
+/// Whether a geometry with the given name has been registered. Returns false 
+/// if Aframe isn't loaded.
+pub fn is_geometry_registered(name: &str) -> bool
+{
+    is_registered(geometries(), name)
+}
+
+/// Checks for `name` in a registry returned by one of the accessors above, 
+/// which each return the `[key, value]` entry of the registry object.
+fn is_registered(entry: Option<JsValue>, name: &str) -> bool
+{
+    entry
+        .map(|entry| entry.unchecked_into::<Array>().get(1))
+        .filter(JsValue::is_object)
+        .and_then(|registry| Reflect::has(&registry, &JsValue::from_str(name)).ok())
+        .unwrap_or(false)
+}
+
+/// Wraps `document.querySelector`, returning the first element matching the 
+/// given selector.
+pub fn query(selector: &str) -> Option<web_sys::Element>
+{
+    web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|document| document.query_selector(selector).ok().flatten())
+}
+
+/// Wraps `document.querySelectorAll`, returning every element matching the 
+/// given selector. Returns an empty `Vec` if the selector is invalid.
+pub fn query_all(selector: &str) -> Vec<web_sys::Element>
+{
+    web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|document| document.query_selector_all(selector).ok())
+        .map(node_list_elements)
+        .unwrap_or_default()
+}
+
+/// Collects the elements in a `NodeList`, skipping any non-element nodes
+pub(crate) fn node_list_elements(nodes: web_sys::NodeList) -> Vec<web_sys::Element>
+{
+    (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|node| node.dyn_into::<web_sys::Element>().ok())
+        .collect()
+}
+
+/// The [three.js](https://threejs.org/) `Object3D` of the entity with the given
+/// id. Returns `None` if the element does not exist or has not been initialized
+/// by aframe yet.
+pub fn entity_object3d(id: &str) -> Option<JsValue>
+{
+    web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .and_then(|element| Reflect::get(&element, &JsValue::from_str("object3D")).ok())
+        .filter(|object3d| !object3d.is_undefined() && !object3d.is_null())
+}
+
+/// The axis-aligned bounding box, in world space, of the 
+/// [three.js](https://threejs.org/) `Object3D` of the entity with the given id
+/// and all of its descendants, as `(min, max)`. Returns `None` if the element 
+/// does not exist or has no loaded geometry yet.
+pub fn entity_bounding_box(id: &str) -> Option<(crate::Vector3, crate::Vector3)>
+{
+    let object3d = entity_object3d(id)?;
+    let box3: Function = Reflect::get(&three_js()?, &JsValue::from_str("Box3")).ok()?.dyn_into().ok()?;
+    let bounds = Reflect::construct(&box3, &Array::new()).ok()?;
+    let set_from_object: Function = Reflect::get(&bounds, &JsValue::from_str("setFromObject")).ok()?.dyn_into().ok()?;
+    set_from_object.call1(&bounds, &object3d).ok()?;
+    let is_empty: Function = Reflect::get(&bounds, &JsValue::from_str("isEmpty")).ok()?.dyn_into().ok()?;
+    if is_empty.call0(&bounds).ok()?.as_bool()?
+    {
+        return None;
+    }
+    let corner = |name: &str| -> Option<crate::Vector3>
+    {
+        let corner = Reflect::get(&bounds, &JsValue::from_str(name)).ok()?;
+        serde_wasm_bindgen::from_value(corner).ok()
+    };
+    Some((corner("min")?, corner("max")?))
+}
+
+/// The pose of the latest WebXR hit-test result found by the `ar-hit-test` 
+/// component of the given `<a-scene>` element, as a world-space position and 
+/// an orientation quaternion `(x, y, z, w)`. This is the pose of the reticle 
+/// Aframe shows over real-world surfaces, so it can be polled each frame to 
+/// place objects. Returns `None` if the component is not active or no surface
+/// has been found.
+pub fn ar_hit_test_pose(scene_el: &web_sys::Element) -> Option<(crate::Vector3, crate::Vector4)>
+{
+    let get = |obj: &JsValue, name: &str| Reflect::get(obj, &JsValue::from_str(name)).ok().filter(JsValue::is_object);
+    let hit_test = get(scene_el, "components").and_then(|components| get(&components, "ar-hit-test"))?;
+    let reticle = get(&hit_test, "bboxMesh")?;
+    if !Reflect::get(&reticle, &JsValue::from_str("visible")).ok()?.is_truthy()
+    {
+        return None;
+    }
+    let coord = |obj: &JsValue, name: &str| Reflect::get(obj, &JsValue::from_str(name)).ok()?.as_f64();
+    let position = get(&reticle, "position")?;
+    let quaternion = get(&reticle, "quaternion")?;
+    Some
+    ((
+        crate::Vector3 { x: coord(&position, "x")?, y: coord(&position, "y")?, z: coord(&position, "z")? },
+        crate::Vector4 { x: coord(&quaternion, "x")?, y: coord(&quaternion, "y")?, z: coord(&quaternion, "z")?, w: coord(&quaternion, "w")? }
+    ))
+}
+
+/// A snapshot of the rendering statistics of a scene, as read by [`scene_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneStats
+{
+    /// Frames rendered per second, as measured by the stats panel
+    pub fps: f64,
+    /// Draw calls issued while rendering the last frame
+    pub draw_calls: u32,
+    /// Triangles rendered in the last frame
+    pub triangles: u32
+}
+
+/// Reads the current rendering statistics of the given `<a-scene>` element 
+/// from its [stats](crate::component::Stats) component and renderer. Useful 
+/// for adapting quality to the device at runtime. Returns `None` if the 
+/// element is not a scene or the stats component is not attached.
+pub fn scene_stats(scene_el: &web_sys::Element) -> Option<SceneStats>
+{
+    let get = |obj: &JsValue, name: &str| Reflect::get(obj, &JsValue::from_str(name)).ok().filter(|val| !val.is_undefined() && !val.is_null());
+    let stats = get(scene_el, "components").and_then(|components| get(&components, "stats"))?;
+    let counter: Function = get(&stats, "stats")?.dyn_into().ok()?;
+    let fps = counter.call1(&JsValue::NULL, &JsValue::from_str("fps")).ok()?;
+    let value: Function = get(&fps, "value")?.dyn_into().ok()?;
+    let render = get(scene_el, "renderer")
+        .and_then(|renderer| get(&renderer, "info"))
+        .and_then(|info| get(&info, "render"))?;
+    let count = |name: &str| get(&render, name).and_then(|val| val.as_f64()).unwrap_or(0.0) as u32;
+    Some(SceneStats
+    {
+        fps: value.call0(&fps).ok()?.as_f64().unwrap_or(0.0),
+        draw_calls: count("calls"),
+        triangles: count("triangles")
+    })
+}
+
+/// Removes the component with the given name from the live element with the 
+/// given id, detaching it entirely rather than resetting it to its defaults.
+/// Returns an error if the element does not exist.
+pub fn remove_component(id: &str, name: &str) -> Result<(), JsValue>
+{
+    web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .ok_or_else(|| JsValue::from_str(&format!("No element with id: {}", id)))?
+        .remove_attribute(name)
+}
+
+/// Turns off fog on the live `<a-scene>` element with the given id by removing
+/// its [fog](crate::component::Fog) component. An empty `fog` attribute would
+/// instead enable fog with its defaults. Returns an error if the element does
+/// not exist.
+pub fn disable_fog(scene_id: &str) -> Result<(), JsValue>
+{
+    remove_component(scene_id, crate::component::Fog::NAME)
+}
+
+/// Calls `refreshObjects` on the [raycaster](crate::component::RayCaster) 
+/// component of the live element with the given id, so that entities added 
+/// since the last refresh can be intersected when `autoRefresh` is disabled. 
+/// Returns an error if the element or its raycaster component does not exist.
+pub fn raycaster_refresh(id: &str) -> Result<(), JsValue>
+{
+    let element = web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .ok_or_else(|| JsValue::from_str(&format!("No element with id: {}", id)))?;
+    let components = Reflect::get(&element, &JsValue::from_str("components"))?;
+    let raycaster = Reflect::get(&components, &JsValue::from_str("raycaster"))?;
+    if !raycaster.is_object()
+    {
+        return Err(JsValue::from_str(&format!("No raycaster on element with id: {}", id)));
+    }
+    let refresh: Function = Reflect::get(&raycaster, &JsValue::from_str("refreshObjects"))?.dyn_into()?;
+    refresh.call0(&raycaster).map(|_| ())
+}
+
+/// Makes the [camera](crate::component::Camera) of the live element with the 
+/// given id the active one, setting `active` to `false` on every other camera
+/// so that only one is active at a time. Returns an error if the element does
+/// not exist or has no camera component.
+pub fn set_active_camera(id: &str) -> Result<(), JsValue>
+{
+    let target = web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .ok_or_else(|| JsValue::from_str(&format!("No element with id: {}", id)))?;
+    if camera_data(&target).is_none()
+    {
+        return Err(JsValue::from_str(&format!("No camera on element with id: {}", id)));
+    }
+    for camera in query_all("[camera]").iter().filter(|camera| **camera != target)
+    {
+        set_camera_active(camera, false)?;
+    }
+    set_camera_active(&target, true)
+}
+
+/// The live element whose [camera](crate::component::Camera) is currently 
+/// active. Returns `None` if no camera has been initialized or all are inactive.
+pub fn active_camera() -> Option<web_sys::Element>
+{
+    query_all("[camera]")
+        .into_iter()
+        .find(|camera| camera_data(camera)
+            .and_then(|data| Reflect::get(&data, &JsValue::from_str("active")).ok())
+            .is_some_and(|active| active.is_truthy()))
+}
+
+/// The parsed data of the camera component of an element, if it has one
+fn camera_data(el: &web_sys::Element) -> Option<JsValue>
+{
+    let get = |obj: &JsValue, name: &str| Reflect::get(obj, &JsValue::from_str(name)).ok().filter(JsValue::is_object);
+    get(el, "components")
+        .and_then(|components| get(&components, "camera"))
+        .and_then(|camera| get(&camera, "data"))
+}
+
+/// Sets the `active` property of the camera component of an element
+fn set_camera_active(el: &web_sys::Element, active: bool) -> Result<(), JsValue>
+{
+    let set_attribute: Function = Reflect::get(el, &JsValue::from_str("setAttribute"))?.dyn_into()?;
+    set_attribute
+        .call3(el, &JsValue::from_str("camera"), &JsValue::from_str("active"), &JsValue::from_bool(active))
+        .map(|_| ())
+}
+
+/// Calls `enterVR` on the given `<a-scene>` element, waiting for the returned 
+/// promise. Returns an error if the element is not a scene or if the promise 
+/// is rejected, e.g. when no headset is available.
+pub async fn enter_vr(scene_el: &web_sys::Element) -> Result<(), JsValue>
+{
+    JsFuture::from(call_scene_method(scene_el, "enterVR")?).await.map(|_| ())
+}
+
+/// Calls `exitVR` on the given `<a-scene>` element, waiting for the returned 
+/// promise. Returns an error if the element is not a scene or if the promise 
+/// is rejected.
+pub async fn exit_vr(scene_el: &web_sys::Element) -> Result<(), JsValue>
+{
+    JsFuture::from(call_scene_method(scene_el, "exitVR")?).await.map(|_| ())
+}
+
+/// Calls `callback` each time the named event is dispatched on `el`, until the
+/// returned handle is dropped.
+pub fn on_event(el: &web_sys::Element, name: &str, callback: impl FnMut(web_sys::Event) + 'static) -> Result<EventListenerHandle, JsValue>
+{
+    let closure = Closure::<dyn FnMut(web_sys::Event)>::new(callback);
+    el.add_event_listener_with_callback(name, closure.as_ref().unchecked_ref())?;
+    Ok(EventListenerHandle { target: el.clone().into(), name: name.to_owned(), closure })
+}
+
+/// An event listener added by [`on_event`]. Dropping the handle removes the 
+/// listener and frees its callback.
+pub struct EventListenerHandle
+{
+    target: web_sys::EventTarget,
+    name: String,
+    closure: Closure<dyn FnMut(web_sys::Event)>
+}
+
+impl Drop for EventListenerHandle
+{
+    fn drop(&mut self)
+    {
+        // Removing a listener that is no longer attached is a no-op
+        let _ = self.target.remove_event_listener_with_callback(&self.name, self.closure.as_ref().unchecked_ref());
+    }
+}
+
+/// Opens the Aframe inspector. If the inspector has already been loaded it is 
+/// opened directly, otherwise the `inspector` component of the first scene 
+/// loads and opens it. Returns an error if neither is available.
+pub fn open_inspector() -> Result<(), JsValue>
+{
+    let inspector = AFRAME.as_ref()
+        .map(|aframe| Reflect::get(&aframe.0, &JsValue::from_str("INSPECTOR")))
+        .transpose()?
+        .filter(|inspector| inspector.is_object());
+    if let Some(inspector) = inspector
+    {
+        let open: Function = Reflect::get(&inspector, &JsValue::from_str("open"))?.dyn_into()?;
+        return open.call0(&inspector).map(|_| ());
+    }
+    let scene = query("a-scene").ok_or_else(|| JsValue::from_str("No scene to open the inspector in"))?;
+    let components = Reflect::get(&scene, &JsValue::from_str("components"))?;
+    let component = Reflect::get(&components, &JsValue::from_str("inspector"))?;
+    let open: Function = Reflect::get(&component, &JsValue::from_str("openInspector"))?.dyn_into()?;
+    open.call0(&component).map(|_| ())
+}
+
+/// Requests an entity from the [pool](crate::component::Pool) component with 
+/// the given name (e.g. `pool__bullets`) on the given scene element. Returns 
+/// `None` if the pool does not exist or is exhausted and not `dynamic`.
+pub fn pool_request(scene_el: &web_sys::Element, pool_name: &str) -> Option<web_sys::Element>
+{
+    call_pool_method(scene_el, pool_name, "requestEntity", &JsValue::UNDEFINED)
+        .ok()
+        .and_then(|entity| entity.dyn_into().ok())
+}
+
+/// Returns an entity previously obtained from [`pool_request`] to the pool 
+/// component with the given name. Returns an error if the pool does not 
+/// exist.
+pub fn pool_return(scene_el: &web_sys::Element, pool_name: &str, el: &web_sys::Element) -> Result<(), JsValue>
+{
+    call_pool_method(scene_el, pool_name, "returnEntity", el).map(|_| ())
+}
+
+/// Calls a method on a named pool component of a scene element
+fn call_pool_method(scene_el: &web_sys::Element, pool_name: &str, method_name: &str, arg: &JsValue) -> Result<JsValue, JsValue>
+{
+    let components = Reflect::get(scene_el, &JsValue::from_str("components"))?;
+    let pool = Reflect::get(&components, &JsValue::from_str(pool_name))?;
+    let method: Function = Reflect::get(&pool, &JsValue::from_str(method_name))?.dyn_into()?;
+    method.call1(&pool, arg)
+}
+
+/// Captures a screenshot of the given scene using its 
+/// [screenshot](crate::component::Screenshot) component, returning it as a 
+/// PNG data URL. Unlike the component's `capture` method, this does not 
+/// download the image. Returns an error if the element is not a scene.
+pub fn take_screenshot(scene_el: &web_sys::Element, projection: crate::component::Projection) -> Result<String, JsValue>
+{
+    let components = Reflect::get(scene_el, &JsValue::from_str("components"))?;
+    let screenshot = Reflect::get(&components, &JsValue::from_str("screenshot"))?;
+    let get_canvas: Function = Reflect::get(&screenshot, &JsValue::from_str("getCanvas"))?.dyn_into()?;
+    let canvas = get_canvas.call1(&screenshot, &JsValue::from_str(&projection.to_string()))?;
+    let to_data_url: Function = Reflect::get(&canvas, &JsValue::from_str("toDataURL"))?.dyn_into()?;
+    to_data_url.call0(&canvas)?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("Screenshot canvas did not produce a data URL"))
+}
+
+/// Calls a method on a scene element, wrapping the result in a promise
+fn call_scene_method(scene_el: &web_sys::Element, method_name: &str) -> Result<Promise, JsValue>
+{
+    let method: Function = Reflect::get(scene_el, &JsValue::from_str(method_name))?.dyn_into()?;
+    Ok(Promise::resolve(&method.call0(scene_el)?))
+}
+
+/// A snapshot of the state of a connected gamepad.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadState
+{
+    /// Index of this gamepad in `navigator.getGamepads()`
+    pub index: u32,
+    /// Identifying string supplied by the browser
+    pub id: String,
+    /// Axis positions, each between `-1.0` and `1.0`
+    pub axes: Vec<f64>,
+    /// Whether each button is currently pressed
+    pub buttons: Vec<bool>
+}
+
+impl From<&web_sys::Gamepad> for GamepadState
+{
+    fn from(gamepad: &web_sys::Gamepad) -> Self 
+    {
+        Self
+        {
+            index: gamepad.index(),
+            id: gamepad.id(),
+            axes: gamepad.axes().iter().filter_map(|axis| axis.as_f64()).collect(),
+            buttons: gamepad.buttons()
+                .iter()
+                .map(|button| button.unchecked_into::<web_sys::GamepadButton>().pressed())
+                .collect()
+        }
+    }
+}
+
+/// Polls `navigator.getGamepads()`, returning the state of every connected 
+/// gamepad. Cheap enough to call from a component's `tick`. Returns an empty
+/// `Vec` when no gamepad is connected or gamepads are unsupported.
+pub fn gamepads() -> Vec<GamepadState>
+{
+    web_sys::window()
+        .and_then(|win| win.navigator().get_gamepads().ok())
+        .map(|gamepads| gamepads
+            .iter()
+            .filter_map(|gamepad| gamepad.dyn_into::<web_sys::Gamepad>().ok())
+            .filter(web_sys::Gamepad::connected)
+            .map(|gamepad| GamepadState::from(&gamepad))
+            .collect())
+        .unwrap_or_default()
+}
+
 pub fn utils() -> Option<JsValue>
 {
     AFRAME.as_ref()
@@ -14,7 +14,7 @@ use wasm_bindgen::{JsCast, prelude::*};
 ///
 /// | field | syntax explanation | description |
 /// |-------|--------------------|-------------|
-/// | schema | A hashmap containing string keys and ComponentProperty values. Recommend the maplit crate | Describes component properties |
+/// | schema | A hashmap containing string keys and AframeProperty values, or an inline `{ name: constructor(default), ... }` block (see `schema!`) | Describes system properties |
 /// | init | JsValue created from a js_sys::Function() | Called on initialization |
 /// | pause | JsValue created from a js_sys::Function() | Called when the entity or scene pauses |
 /// | play | JsValue created from a js_sys::Function() | Called when the entity or scene resumes |
@@ -23,18 +23,19 @@ use wasm_bindgen::{JsCast, prelude::*};
 ///
 /// All parameteres are optional, although the order must be exactly as shown. 
 /// `schema` should be a HashMap with string keys and `AframeProperty` 
-/// values. The rest are strings containing  javascript code. A `js!` macro 
-/// is provided to allow inline javascript code to be included in the Rust code
-/// (See the docs for the `js!` macro for caveats and limitations). Here's an 
-/// example:
+/// values, or a braced list of `name: constructor(default)` entries as 
+/// accepted by `schema!`. The rest are strings containing javascript code. A 
+/// `js!` macro is provided to allow inline javascript code to be included in 
+/// the Rust code (See the docs for the `js!` macro for caveats and 
+/// limitations). Here's an example:
 /// ```ignore
 /// // Example: 
 /// let some_system = system_def!
 /// (
-///     schema: hashmap!
+///     schema: 
 ///     {
-///         "some_float" => AframeProperty::float("number", None),
-///         "some_text" => AframeProperty::string("string", Some(Cow::Borrowed("init")))
+///         "some_float": number(),
+///         "some_text": string("init")
 ///     },
 ///     init: js!
 ///     (
@@ -52,12 +53,23 @@ use wasm_bindgen::{JsCast, prelude::*};
 /// );
 /// unsafe
 /// {
-///     some_system.register("system_name");
+///     some_system.register("system_name")?;
 /// }
 /// ```
 #[macro_export]
 macro_rules! system_def
 {
+    (
+        schema: { $($schema:tt)* },
+        $($rest:tt)*
+    ) => 
+    {
+        $crate::system_def!
+        (
+            schema: $crate::schema!($($schema)*),
+            $($rest)*
+        )
+    };
     (
         $(schema: $schema:expr,)?
         $(init: $init:expr,)?
@@ -118,7 +130,7 @@ impl From<&SystemReg> for JsValue
 {
     fn from(sysr: &SystemReg) -> Self 
     {
-        let js_value = serde_wasm_bindgen::to_value(sysr).expect("Failed to convert SystemReg into JsObject");
+        let js_value = to_js_object(sysr).expect("Failed to convert SystemReg into JsObject");
         define_property(js_value.unchecked_ref(), "init", (sysr.init).unchecked_ref());
         define_property(js_value.unchecked_ref(), "pause", (sysr.pause).unchecked_ref());
         define_property(js_value.unchecked_ref(), "play", (sysr.play).unchecked_ref());
@@ -132,9 +144,13 @@ impl From<&SystemReg> for JsValue
 }
 impl SystemReg
 {
-    /// Register a system in aframe. Warning: Aframe must be initialized before this is called.
-    pub unsafe fn register(self, name: &str)
+    /// Register a system in aframe. 
+    ///
+    /// # Safety
+    /// Aframe must be initialized before this is called.
+    pub unsafe fn register(self, name: &str) -> Result<(), RegError>
     {
-        registerSystem(name, (&self).into());
+        registerSystem(name, (&self).into())?;
+        Ok(())
     }
 }
\ No newline at end of file
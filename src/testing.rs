@@ -0,0 +1,39 @@
+//! Helpers for asserting that components serialize correctly. Intended for 
+//! crates which define their own components with `component_struct!`. 
+//! Requires the `testing` feature.
+
+use std::{borrow::Cow, collections::HashMap};
+use crate::component::Component;
+
+/// Asserts that a component is displayed as the expected attribute value.
+pub fn assert_component_serializes(cmp: &dyn Component, expected: &str)
+{
+    let actual = cmp.to_string();
+    assert_eq!
+    (
+        actual, expected, 
+        "component {:?} serialized as `{}`, expected `{}`", cmp, actual, expected
+    );
+}
+
+/// Parses the displayed attribute value of a multi-property component back 
+/// into a map, asserting that each property agrees with `as_map`. Properties 
+/// left at their defaults are not displayed, so only the displayed properties 
+/// are compared. Returns the result of `as_map`. 
+pub fn roundtrip_map(cmp: &dyn Component) -> HashMap<Cow<'static, str>, Cow<'static, str>>
+{
+    let map = cmp.as_map();
+    let displayed = cmp.to_string();
+    for property in crate::component::split_properties(&displayed)
+    {
+        let (key, value) = property.split_once(':')
+            .unwrap_or_else(|| panic!("component {:?} displayed `{}`, which is not a property list", cmp, displayed));
+        let (key, value) = (key.trim(), value.trim());
+        assert_eq!
+        (
+            map.get(key).map(Cow::as_ref), Some(value), 
+            "property `{}` of component {:?} does not match as_map", key, cmp
+        );
+    }
+    map
+}
@@ -33,7 +33,7 @@ async fn test_scene_creation()
     let scene = scene!
     {
         // TODO: Some of these attributes are actually components
-        attributes: ("inspector", "true"), ("embedded", "true"), ("cursor", "rayOrigin: mouse"),
+        attributes: ("cursor", "rayOrigin: mouse"),
                     ("mixin", "intersect_ray"), ("crawling-cursor", "target: #mouse-cursor"), 
                     ("style", "min-height: 50px;"),
         assets: assets!
@@ -41,13 +41,10 @@ async fn test_scene_creation()
             mixin!
             {
                 "intersect_ray", 
-                ("raycaster", component!
-                {
-                    RayCaster,
-                    objects: List(Cow::Borrowed(&[Cow::Borrowed("#ramen-cube, #water")]))
-                })
+                ("raycaster", RayCaster::targeting([Selector::id("ramen-cube"), Selector::id("water")]))
             }
         },
+        components: ("inspector", component!(component::Inspector)), ("embedded", component!(component::Embedded)),
         children: 
         // The mouse cursor
         entity!
@@ -160,17 +157,7 @@ async fn test_scene_creation()
             attributes: ("id", "cube-rig"),
             components: 
             ("position", component::Position{x: 0.0, y: 2.5, z: -2.0}),
-            ("light", component!
-            {
-                component::Light,
-                light_type: component::LightType::Point
-                {
-                    decay: 1.0,
-                    distance: 50.0,
-                    shadow: component::OptionalLocalShadow::NoCast{},
-                }, 
-                intensity: 0.0
-            }),
+            ("light", component::Light::point(color::WHITE, 0.0, 50.0)),
             ("animation__mouseenter", component!
             {
                 component::Animation,
@@ -212,13 +199,7 @@ async fn test_scene_creation()
         entity!
         {
             attributes: ("id", "ambient-light"),
-            components: ("light", component!
-            {
-                component::Light,
-                light_type: component::LightType::Ambient{},
-                color: color::GREY73,
-                intensity: 0.2
-            })
+            components: ("light", component::Light::ambient(color::GREY73, 0.2))
         },
         // Directional light
         entity!
@@ -226,27 +207,30 @@ async fn test_scene_creation()
             attributes: ("id", "directional-light"),
             components: 
             ("position", component::Position{ x: 0.5, y: 1.0, z: 1.0 }),
-            ("light", component!
-            {
-                component::Light,
-                light_type: component::LightType::Directional
-                {
-                    shadow: component::OptionalDirectionalShadow::Cast
-                    {
-                        shadow: component!
-                        {
-                            component::DirectionalShadow
-                        }
-                    }
-                },
-                color: color::WHITE,
-                intensity: 0.1
-            })
+            ("light", component::Light::directional(color::WHITE, 0.1, true))
         }
     };
 
     let body = web_sys::window().and_then(|win| win.document()).unwrap().body().unwrap();
-    body.append_with_node_1(scene.as_element().unwrap().as_ref()).unwrap();
+    let scene_element = scene.as_element().unwrap();
+    body.append_with_node_1(scene_element.as_ref()).unwrap();
+    Scene::wait_until_loaded(&scene_element).await.unwrap();
+    // Already loaded, so this should resolve immediately
+    Scene::wait_until_loaded(&scene_element).await.unwrap();
+    let assets_element = scene_element.query_selector("a-assets").unwrap().unwrap();
+    Assets::wait_loaded(&assets_element).await.unwrap();
+
+    let cameras = Scene::entities_with_component(&scene_element, "camera");
+    assert_eq!(cameras.len(), 1);
+    assert_eq!(cameras[0].id(), "camera");
+    sys::set_active_camera("camera").unwrap();
+    assert_eq!(sys::active_camera().map(|camera| camera.id()).as_deref(), Some("camera"));
+    assert_eq!(sys::query_all("#left-controller, #right-controller").len(), 2);
+
+    // Not in VR, so exiting resolves without doing anything
+    sys::exit_vr(&scene_element).await.unwrap();
+    let not_a_scene = web_sys::window().and_then(|win| win.document()).unwrap().create_element("div").unwrap();
+    assert!(sys::enter_vr(&not_a_scene).await.is_err());
 }
 
 #[wasm_bindgen_test]
@@ -254,13 +238,14 @@ async fn test_register_component()
 {
     init_aframe_tests().await;
 
-    let mut schema = HashMap::new();
-    schema.insert("updateFreq", AframeProperty::number(Some(500.0)));
-
     let fps = component_def!
     {
         dependencies: "text";
-        schema: schema,
+        schema: 
+        {
+            updateFreq: number(500.0),
+            textColor: color_rgb(color::LIGHTBLUE)
+        },
         init: js!(this.timeOfLastUpdate = 0.0;),
         tick: js!
         (time, delta =>>
@@ -273,11 +258,192 @@ async fn test_register_component()
     };
     unsafe 
     {
-        fps.register("fps");
+        fps.register("fps").unwrap();
     }
     console_log!("Registered fps component.");
 }
 
+#[wasm_bindgen_test]
+async fn test_disable_fog() 
+{
+    init_aframe_tests().await;
+
+    let scene = scene!
+    {
+        attributes: ("id", "foggy-scene"), ("embedded", "true"),
+        assets: assets!(),
+        components: (component::Fog::NAME, component!(component::Fog, fog_type: component::FogType::Exponential { density: 0.5 }))
+    };
+    let body = web_sys::window().and_then(|win| win.document()).unwrap().body().unwrap();
+    let scene_element = scene.as_element().unwrap();
+    body.append_with_node_1(scene_element.as_ref()).unwrap();
+    Scene::wait_until_loaded(&scene_element).await.unwrap();
+
+    let fog_component = || 
+    {
+        let components = js_sys::Reflect::get(&scene_element, &JsValue::from_str("components")).unwrap();
+        js_sys::Reflect::get(&components, &JsValue::from_str("fog")).unwrap()
+    };
+    assert!(scene_element.has_attribute("fog"));
+    assert!(!fog_component().is_undefined());
+    sys::disable_fog("foggy-scene").unwrap();
+    assert!(!scene_element.has_attribute("fog"));
+    assert!(fog_component().is_undefined());
+    assert!(sys::disable_fog("no-such-scene").is_err());
+    scene_element.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_register_event_listener_component() 
+{
+    init_aframe_tests().await;
+
+    let hide_on_click = component_def!
+    {
+        init: add_event_listener!(this, "click", this.el.setAttribute("visible", false);),
+    };
+    let mark_on_click = component_def!
+    {
+        init: add_event_listener!(this, "click", e =>> this.el.setAttribute("data-clicked", e.type);),
+    };
+    unsafe 
+    {
+        hide_on_click.register("hide-on-click").unwrap();
+        mark_on_click.register("mark-on-click").unwrap();
+    }
+    console_log!("Registered hide-on-click and mark-on-click components.");
+
+    let scene = scene!
+    {
+        attributes: ("embedded", "true"),
+        assets: assets!(),
+        children: entity!(attributes: ("id", "clickable"), ("hide-on-click", ""), ("mark-on-click", ""))
+    };
+    let body = web_sys::window().and_then(|win| win.document()).unwrap().body().unwrap();
+    let scene_element = scene.as_element().unwrap();
+    body.append_with_node_1(scene_element.as_ref()).unwrap();
+    Scene::wait_until_loaded(&scene_element).await.unwrap();
+
+    let clickable = sys::query("#clickable").unwrap();
+    assert_eq!(clickable.get_attribute("data-clicked"), None);
+    clickable.dispatch_event(&web_sys::Event::new("click").unwrap()).unwrap();
+    assert_eq!(clickable.get_attribute("data-clicked").as_deref(), Some("click"));
+    let object3d = sys::entity_object3d("clickable").unwrap();
+    assert_eq!(js_sys::Reflect::get(&object3d, &JsValue::from_str("visible")).unwrap().as_bool(), Some(false));
+    scene_element.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_register_all_components() 
+{
+    init_aframe_tests().await;
+
+    let result = unsafe 
+    {
+        ComponentReg::register_all(&[
+            ("bulk-a", component_def!{ init: js!(this.el.setAttribute("id", "bulk-a");), }),
+            ("bulk-b", component_def!{ init: js!(this.el.setAttribute("id", "bulk-b");), }),
+            ("bulk-a", component_def!{ init: js!(this.el.setAttribute("id", "bulk-a");), }),
+        ])
+    };
+    let errors = result.expect_err("Registering a duplicate component should fail");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "bulk-a");
+    console_log!("Registered bulk-a and bulk-b components.");
+}
+
+#[wasm_bindgen_test]
+async fn test_registry()
+{
+    init_aframe_tests().await;
+
+    let registry = Registry::new()
+        .system("registry-sys", system_def!{ init: js!(this.initialized = true;), })
+        .component("registry-cmp", component_def!{ init: js!(this.el.setAttribute("id", "registry-cmp");), })
+        .component("registry-cmp", component_def!{ multiple: true, })
+        .component("position", component_def!{});
+    let report = unsafe { registry.register_all() };
+    assert!(report.failed.is_empty());
+    assert_eq!(report.registered, vec!("registry-sys", "registry-cmp"));
+    assert_eq!(report.skipped, vec!("position"));
+
+    let report = unsafe { registry.register_all() };
+    assert!(report.registered.is_empty());
+    assert_eq!(report.skipped, vec!("registry-sys", "registry-cmp", "position"));
+}
+
+const PORTAL_VS: &str = "
+varying vec3 vWorldPosition;
+void main() 
+{
+    vec4 worldPosition = modelMatrix * vec4(position, 1.0);
+    vWorldPosition = worldPosition.xyz;
+    gl_Position = projectionMatrix * modelViewMatrix * vec4(position, 1.0);
+}";
+
+const PORTAL_FS: &str = "
+uniform vec3 backgroundColor;
+uniform vec3 strokeColor;
+uniform int borderEnabled;
+void main() 
+{
+    gl_FragColor = vec4(borderEnabled == 1 ? strokeColor : backgroundColor, 1.0);
+}";
+
+#[wasm_bindgen_test]
+async fn test_register_portal_shader()
+{
+    init_aframe_tests().await;
+
+    let shaders = js_sys::Reflect::get(&web_sys::window().unwrap().get("AFRAME").unwrap(), &"shaders".into()).unwrap();
+    assert!(js_sys::Reflect::has(&shaders, &shader::PORTAL.into()).unwrap());
+    unsafe
+    {
+        Shader::portal(PORTAL_VS.into(), PORTAL_FS.into()).register("custom-portal").unwrap();
+    }
+    assert!(js_sys::Reflect::has(&shaders, &"custom-portal".into()).unwrap());
+
+    let entity = entity!
+    {
+        attributes: ("id", "portal-entity"),
+        components: ("material", component!
+        {
+            component::Material,
+            shader: component::MaterialShader::custom("custom-portal"),
+            props: component::MaterialProps(Cow::Borrowed(&[(Cow::Borrowed("borderEnabled"), Cow::Borrowed("0"))]))
+        })
+    };
+    let body = web_sys::window().and_then(|win| win.document()).unwrap().body().unwrap();
+    body.append_with_node_1(entity.as_element().unwrap().as_ref()).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn test_component_reg_shape()
+{
+    use js_sys::{Array, Object, Reflect};
+
+    let minimal: JsValue = (&component_def!{}).into();
+    assert!(!Reflect::has(&minimal, &"multiple".into()).unwrap());
+    let schema = Reflect::get(&minimal, &"schema".into()).unwrap();
+    assert_eq!(Object::get_prototype_of(&schema), Object::get_prototype_of(&Object::new()));
+    assert_eq!(Object::keys(schema.unchecked_ref::<Object>()).length(), 0);
+    let dependencies = Reflect::get(&minimal, &"dependencies".into()).unwrap();
+    assert!(Array::is_array(&dependencies));
+    assert_eq!(Array::from(&dependencies).length(), 0);
+
+    let full: JsValue = (&component_def!
+    {
+        dependencies: "text";
+        schema: { "updateFreq": number(500.0) },
+        multiple: true,
+    }).into();
+    assert_eq!(Reflect::get(&full, &"multiple".into()).unwrap(), JsValue::TRUE);
+    let schema = Reflect::get(&full, &"schema".into()).unwrap();
+    let update_freq = Reflect::get(&schema, &"updateFreq".into()).unwrap();
+    assert_eq!(Reflect::get(&update_freq, &"type".into()).unwrap(), "number");
+    assert_eq!(Reflect::get(&update_freq, &"default".into()).unwrap(), 500.0);
+}
+
 #[wasm_bindgen_test]
 async fn test_register_geometry() 
 {
@@ -298,23 +464,49 @@ async fn test_register_geometry()
     };
     unsafe 
     {
-        newbox.register("newbox");
+        newbox.register("newbox").unwrap();
     }
     console_log!("Registered newbox geometry.");
 }
 
 #[wasm_bindgen_test]
-async fn test_register_system() 
+async fn test_register_element() 
 {
     init_aframe_tests().await;
 
-    let mut schema = HashMap::new();
-    schema.insert("some_float", AframeProperty::number(None));
-    schema.insert("some_text", AframeProperty::string(Some(Cow::Borrowed("init"))));
+    let element = primitive::PrimitiveElementReg
+    {
+        connected: js!(this.setAttribute("data-attached", "true");).into(),
+        disconnected: js!(this.setAttribute("data-attached", "false");).into(),
+        ..primitive::PrimitiveElementReg::default()
+    };
+    unsafe 
+    {
+        element.register_element("a-lifecycle-test").unwrap();
+    }
+    console_log!("Registered a-lifecycle-test element.");
+
+    let body = web_sys::window().and_then(|win| win.document()).unwrap().body().unwrap();
+    let instance = web_sys::window().and_then(|win| win.document()).unwrap().create_element("a-lifecycle-test").unwrap();
+    assert_eq!(instance.get_attribute("data-attached"), None);
+    body.append_with_node_1(&instance).unwrap();
+    assert_eq!(instance.get_attribute("data-attached").as_deref(), Some("true"));
+    instance.remove();
+    assert_eq!(instance.get_attribute("data-attached").as_deref(), Some("false"));
+}
+
+#[wasm_bindgen_test]
+async fn test_register_system() 
+{
+    init_aframe_tests().await;
 
     let my_sys = system_def!
     {
-        schema: schema,
+        schema: 
+        {
+            "some_float": number(),
+            "some_text": string("init")
+        },
         init: js!
         (
             this.data.some_float = 1.0; 
@@ -331,7 +523,7 @@ async fn test_register_system()
     };
     unsafe 
     {
-        my_sys.register("my-sys");
+        my_sys.register("my-sys").unwrap();
     }
     console_log!("Registered my-sys system.");
 }
@@ -357,9 +549,73 @@ async fn test_globals_access()
     // console_log!("is_oculus_go: {:?}", sys::isOculusGo());
     // console_log!("is_mobile: {:?}", sys::isMobile());
     console_log!("Aframe version: {:?}", sys::version().expect("version access failed!"));
+    assert!(sys::is_component_registered(component::Material::NAME));
+    assert!(sys::is_shader_registered("flat"));
+    assert!(sys::is_primitive_registered("a-box"));
+    assert!(sys::is_geometry_registered("sphere"));
+    assert!(!sys::is_component_registered("not-a-component"));
     console_log!("Globals access test complete.");
 }
 
+#[wasm_bindgen_test]
+async fn test_dom_access() 
+{
+    init_aframe_tests().await;
+
+    assert!(sys::query("#no-such-element").is_none());
+    assert!(sys::entity_object3d("no-such-element").is_none());
+    assert!(sys::entity_bounding_box("no-such-element").is_none());
+    assert!(sys::query("head").is_some());
+    assert!(sys::query_all(".no-such-class").is_empty());
+    assert!(sys::query_all("[invalid").is_empty());
+    // No gamepads are connected in a headless browser
+    assert!(sys::gamepads().is_empty());
+    assert!(Entity::set_visible_in_dom("no-such-element", false).is_err());
+    assert!(sys::remove_component("no-such-element", "fog").is_err());
+    assert!(sys::raycaster_refresh("no-such-element").is_err());
+    assert!(sys::set_active_camera("no-such-element").is_err());
+    assert!(Entity::apply_to_dom("no-such-element", &[]).is_err());
+    let head = sys::query("head").unwrap();
+    assert!(sys::pool_request(&head, "pool__none").is_none());
+    assert!(sys::pool_return(&head, "pool__none", &head).is_err());
+    assert!(sys::take_screenshot(&head, component::Projection::Perspective).is_err());
+    // Not an AR session, so there is no hit-test pose
+    assert!(sys::ar_hit_test_pose(&head).is_none());
+    // Not a scene, so there are no stats to read
+    assert!(sys::scene_stats(&head).is_none());
+    console_log!("DOM access test complete.");
+}
+
+#[wasm_bindgen_test]
+async fn test_event_listener_handle() 
+{
+    use std::{cell::Cell, rc::Rc};
+    init_aframe_tests().await;
+
+    let el = web_sys::window().and_then(|win| win.document()).unwrap().create_element("a-entity").unwrap();
+    let clicks = Rc::new(Cell::new(0));
+    let counter = clicks.clone();
+    let handle = sys::on_event(&el, "click", move |_| counter.set(counter.get() + 1)).unwrap();
+    let click = || el.dispatch_event(&web_sys::Event::new("click").unwrap()).unwrap();
+    click();
+    click();
+    assert_eq!(clicks.get(), 2);
+    drop(handle);
+    click();
+    assert_eq!(clicks.get(), 2);
+}
+
+#[test]
+fn entity_tags()
+{
+    let plain = entity!(attributes: ("id", "plain"));
+    assert_eq!(plain.tag(), "a-entity");
+    assert_eq!(plain.as_raw_html(), r#"<a-entity id="plain"></a-entity>"#);
+    let ball = entity!(primitive: primitive::A_SPHERE, attributes: ("id", "ball"));
+    assert_eq!(ball.tag(), "a-sphere");
+    assert_eq!(ball.as_raw_html(), r#"<a-sphere id="ball"></a-sphere>"#);
+}
+
 #[test]
 fn entity_cmp()
 {
@@ -391,4 +647,1252 @@ fn entity_cmp()
         vec!((Cow::Borrowed("test_cmp_2"), Box::new(component!(Position, x: 1.0, y: 2.0, z: 3.0)))), 
         vec!()
     ));
-}
\ No newline at end of file
+}
+#[test]
+fn component_vec_cmp()
+{
+    let cmps = ComponentVec(vec!
+    (
+        (Cow::Borrowed("position"), Box::new(component!(Position, x: 1.0, y: 2.0, z: 3.0)) as Box<dyn Component>),
+        (Cow::Borrowed("rotation"), Box::new(component!(Rotation, y: 45.0)))
+    ));
+
+    assert_eq!(cmps, cmps.clone());
+    assert_ne!(cmps, ComponentVec(vec!
+    (
+        (Cow::Borrowed("position"), Box::new(component!(Position, x: 1.0, y: 2.0, z: 3.0)) as Box<dyn Component>),
+        (Cow::Borrowed("rotation"), Box::new(component!(Rotation, y: 90.0)))
+    )));
+    assert_ne!(cmps, ComponentVec(vec!
+    (
+        (Cow::Borrowed("position"), Box::new(component!(Position, x: 1.0, y: 2.0, z: 3.0)) as Box<dyn Component>)
+    )));
+}
+
+#[test]
+fn component_eq_symmetry()
+{
+    let position: Box<dyn Component> = Box::new(component!(Position, x: 1.0, y: 2.0, z: 3.0));
+    let rotation: Box<dyn Component> = Box::new(component!(Rotation, x: 1.0, y: 2.0, z: 3.0));
+    let camera: Box<dyn Component> = Box::new(component!(component::Camera));
+
+    // Same values, different types
+    assert!(!position.eq(&*rotation));
+    assert_eq!(position.eq(&*rotation), rotation.eq(&*position));
+    assert_eq!(position.eq(&*camera), camera.eq(&*position));
+
+    // Same type, same values
+    let position_2: Box<dyn Component> = Box::new(component!(Position, x: 1.0, y: 2.0, z: 3.0));
+    assert!(position.eq(&*position_2));
+    assert!(position_2.eq(&*position));
+    assert!(camera.eq(&*camera.clone()));
+
+    // Same type, different values
+    let position_3: Box<dyn Component> = Box::new(component!(Position, x: 3.0, y: 2.0, z: 1.0));
+    assert!(!position.eq(&*position_3));
+    assert!(!position_3.eq(&*position));
+}
+
+#[test]
+fn entity_pretty_html()
+{
+    let ent = Entity::new
+    (
+        vec!(Attribute::new("id", "parent")), 
+        vec!(), 
+        vec!(Entity::new(vec!(Attribute::new("id", "child")), vec!(), vec!(Entity::default())))
+    );
+
+    assert_eq!(ent.as_pretty_html(2), "\
+<a-entity id=\"parent\">
+  <a-entity id=\"child\">
+    <a-entity></a-entity>
+  </a-entity>
+</a-entity>
+");
+}
+
+#[test]
+fn compact_raw_html()
+{
+    assert_eq!(Entity::default().as_raw_html(), "<a-entity></a-entity>");
+    assert_eq!
+    (
+        Entity::new(vec!(Attribute::new("id", "parent")), vec!(), vec!(Entity::default())).as_raw_html(),
+        "<a-entity id=\"parent\"><a-entity></a-entity></a-entity>"
+    );
+    assert_eq!(Scene::default().as_raw_html(), "<a-scene><a-assets></a-assets></a-scene>");
+}
+
+#[test]
+fn entity_component_attr_cache()
+{
+    let mut ent = Entity::with_components(vec!((Cow::Borrowed("position"), Box::new(component!(Position, x: 1.0)))));
+    assert_eq!(ent.as_raw_html(), "<a-entity position=\"1 0 0\"></a-entity>");
+    assert_eq!(ent.as_raw_html(), "<a-entity position=\"1 0 0\"></a-entity>");
+
+    ent.components_mut()[0].1 = Box::new(component!(Position, x: 2.0));
+    assert_eq!(ent.as_raw_html(), "<a-entity position=\"2 0 0\"></a-entity>");
+    assert_eq!(ent, Entity::with_components(vec!((Cow::Borrowed("position"), Box::new(component!(Position, x: 2.0))))));
+}
+
+#[test]
+fn multiple_component_ids()
+{
+    assert_eq!(component_id!("sound", "ambient"), "sound__ambient");
+    assert_eq!(component_id!("animation", "mouse-enter_2"), "animation__mouse-enter_2");
+
+    assert!(!component::is_valid_component_id_part(""));
+    assert!(!component::is_valid_component_id_part("_ambient"));
+    assert!(!component::is_valid_component_id_part("ambient_"));
+    assert!(!component::is_valid_component_id_part("amb__ient"));
+    assert!(!component::is_valid_component_id_part("amb ient"));
+    assert!(!component::is_valid_component_id_part("amb=ient"));
+
+    let ent = entity!
+    {
+        components: 
+        (component_id!("sound", "ambient"), component!(component::Sound, volume: 0.5)),
+        (component_id!("sound", "click"), component!(component::Sound, volume: 0.25))
+    };
+    assert_eq!(ent.components()[0].0, "sound__ambient");
+    assert_eq!(ent.components()[1].0, "sound__click");
+}
+
+#[test]
+fn ord_attribute()
+{
+    use std::collections::{BTreeSet, HashSet};
+
+    let attrs = vec!
+    (
+        Attribute::new("id", "b"),
+        Attribute::new("class", "x"),
+        Attribute::new("id", "a"),
+        Attribute::new("class", "x")
+    );
+    let sorted: BTreeSet<OrdAttribute> = attrs.iter().cloned().map(OrdAttribute::from).collect();
+    assert_eq!
+    (
+        sorted.into_iter().map(Attribute::from).collect::<Vec<_>>(), 
+        vec!(Attribute::new("class", "x"), Attribute::new("id", "a"), Attribute::new("id", "b"))
+    );
+    let unique: HashSet<OrdAttribute> = attrs.into_iter().map(OrdAttribute).collect();
+    assert_eq!(unique.len(), 3);
+    assert_eq!(OrdAttribute(Attribute::new("id", "a")).to_string(), "id=\"a\"");
+}
+
+#[test]
+fn set_and_get_attr()
+{
+    let mut ent = Entity::new
+    (
+        vec!(Attribute::new("id", "a"), Attribute::new("class", "x"), Attribute::new("id", "b")), 
+        vec!(), 
+        vec!()
+    );
+    assert_eq!(ent.get_attr("id"), Some("a"));
+    assert_eq!(ent.get_attr("missing"), None);
+
+    assert_eq!(ent.set_attr("id", "c"), Some(Cow::Borrowed("a")));
+    assert_eq!(ent.attributes(), &vec!(Attribute::new("id", "c"), Attribute::new("class", "x")));
+    assert_eq!(ent.set_attr("visible", "false"), None);
+    assert_eq!(ent.get_attr("visible"), Some("false"));
+
+    let mut scene = Scene::default();
+    scene.set_attr("embedded", "");
+    assert_eq!(scene.get_attr("embedded"), Some(""));
+}
+
+#[test]
+fn text_negate_and_shader()
+{
+    assert_eq!(component!(component::Text).to_string(), "");
+    assert_eq!
+    (
+        component!(component::Text, negate: false, shader: component::TextShader::Msdf).to_string(), 
+        "negate: false;shader: msdf;"
+    );
+}
+
+#[test]
+fn easing_functions()
+{
+    assert_eq!(component::Easing::EaseOutCubic.to_string(), "easeOutCubic");
+    assert_eq!(component::Easing::Steps(4).to_string(), "steps(4)");
+    assert_eq!(component::Easing::CubicBezier(0.5, 0.05, 0.1, 0.3).to_string(), "cubicBezier(0.5,0.05,0.1,0.3)");
+    assert_eq!
+    (
+        component!(component::Animation, easing: component::Easing::Steps(4)).to_string(), 
+        "easing: steps(4);"
+    );
+}
+
+#[test]
+fn typed_color_property()
+{
+    assert_eq!
+    (
+        serde_json::to_string(&AframeProperty::color_rgb(Some(color::RED))).unwrap(),
+        r##"{"type":"color","default":"#ff0000"}"##
+    );
+    assert_eq!
+    (
+        serde_json::to_string(&AframeProperty::color_rgb(None)).unwrap(),
+        r##"{"type":"color"}"##
+    );
+}
+
+#[test]
+fn assets_lookup()
+{
+    let assets = assets!
+    {
+        Image::new("ramen", "/pics/ramen.png"),
+        Audio::new("ambient_music", "/audio/ambient.mp3"),
+        mixin!("intersect_ray", ("raycaster", component!(RayCaster)))
+    };
+
+    assert_eq!(assets.get("ramen"), Some(&Image::new("ramen", "/pics/ramen.png")));
+    assert_eq!(assets.get("ambient_music").map(Asset::src), Some(&Cow::Borrowed("/audio/ambient.mp3")));
+    assert!(matches!(assets.get("intersect_ray"), Some(Asset::Mixin(_))));
+    assert_eq!(assets.get("missing"), None);
+
+    assert_eq!(assets.iter().map(Asset::id).collect::<Vec<_>>(), vec!("ramen", "ambient_music", "intersect_ray"));
+    assert_eq!((&assets).into_iter().count(), 3);
+    assert_eq!(assets.into_iter().count(), 3);
+}
+
+#[test]
+fn selectors()
+{
+    assert_eq!(Selector::id("music").to_string(), "#music");
+    assert_eq!(Selector::class("clickable").to_string(), ".clickable");
+    assert_eq!(Selector::raw("a-entity > [geometry]").to_string(), "a-entity > [geometry]");
+    assert_eq!
+    (
+        component!(RayCaster, objects: List(Cow::Owned(vec!(Selector::id("ramen-cube"), Selector::class("water"))))).to_string(),
+        "objects: #ramen-cube,.water;"
+    );
+    assert_eq!(RayCaster::targeting([Selector::id("ramen-cube"), Selector::class("water")]).to_string(), "objects: #ramen-cube,.water;");
+    assert_eq!(RayCaster::targeting([]), RayCaster::DEFAULT);
+    assert_eq!(RayCaster::targeting([]).to_string(), "");
+}
+
+#[test]
+fn transform_components()
+{
+    let transform = component!
+    (
+        component::Transform, 
+        position: component!(Position, y: 1.5), 
+        rotation: component!(Rotation, y: 45.0)
+    );
+    assert_eq!
+    (
+        Entity::with_components(transform.into_components()),
+        Entity::with_components(vec!
+        (
+            (Cow::Borrowed("position"), Box::new(component!(Position, y: 1.5))),
+            (Cow::Borrowed("rotation"), Box::new(component!(Rotation, y: 45.0))),
+            (Cow::Borrowed("scale"), Box::new(component::Scale { x: 1.0, y: 1.0, z: 1.0 }))
+        ))
+    );
+}
+
+#[test]
+fn look_at()
+{
+    assert_eq!
+    (
+        component!(component::LookAt, target: component::LookAtTarget::Selector { selector: Selector::id("camera") }).to_string(), 
+        "#camera"
+    );
+    assert_eq!
+    (
+        component!(component::LookAt, target: component::LookAtTarget::Position { position: Vector3 { x: 0.0, y: 1.6, z: -1.0 } }).to_string(), 
+        "0 1.6 -1"
+    );
+}
+
+#[test]
+fn entity_grid()
+{
+    let tiles = entity::grid(3, 2, Vector3 { x: 2.0, y: 0.0, z: 1.5 }, |col, row| 
+    {
+        if col == 1 && row == 1
+        {
+            Entity::with_components(vec!((Cow::Borrowed("position"), Box::new(component!(Position, y: 1.0)))))
+        }
+        else
+        {
+            Entity::new(vec!(Attribute::new("id", format!("tile-{}-{}", col, row))), vec!(), vec!())
+        }
+    });
+
+    assert_eq!(tiles.len(), 6);
+    assert_eq!(tiles[0].as_raw_html(), "<a-entity position=\"0 0 0\" id=\"tile-0-0\"></a-entity>");
+    assert_eq!(tiles[2].as_raw_html(), "<a-entity position=\"4 0 0\" id=\"tile-2-0\"></a-entity>");
+    assert_eq!(tiles[3].as_raw_html(), "<a-entity position=\"0 0 1.5\" id=\"tile-0-1\"></a-entity>");
+    assert_eq!(tiles[4].as_raw_html(), "<a-entity position=\"2 1 1.5\"></a-entity>");
+}
+
+#[test]
+fn xr_features()
+{
+    let webxr = component!
+    (
+        WebXR, 
+        required_features: List(Cow::Borrowed(&[XrFeature::LocalFloor, XrFeature::HitTest])),
+        optional_features: List(Cow::Owned(vec!(XrFeature::HandTracking, XrFeature::Raw(Cow::Borrowed("mesh-detection")))))
+    );
+    assert_eq!(webxr.to_string(), "requiredFeatures: local-floor,hit-test;optionalFeatures: hand-tracking,mesh-detection;");
+    assert_eq!(serde_json::to_string(&XrFeature::DomOverlay).unwrap(), "\"dom-overlay\"");
+}
+
+#[test]
+fn fog_display()
+{
+    assert_eq!(component!(Fog).to_string(), "");
+    assert_eq!(component!(Fog, fog_type: FogType::Exponential { density: 0.5 }).to_string(), "type: exponential;density: 0.5;");
+    assert_eq!(component!(Fog, color: color::WHITE).to_string(), "color: #ffffff;");
+}
+
+#[test]
+fn float_formatting()
+{
+    assert_eq!(fmt_float(0.0000001), "0");
+    assert_eq!(fmt_float(-0.0000001), "0");
+    assert_eq!(fmt_float(0.001), "0.001");
+    assert_eq!(fmt_float(1e20), "100000000000000000000");
+    assert_eq!(fmt_float(0.1 + 0.2), "0.3");
+    assert_eq!(Vector3 { x: 0.0000001, y: 1.5, z: -2.0 }.to_string(), "0 1.5 -2");
+    assert_eq!(component!(Position, x: 0.0000001, y: 0.1, z: 3.0).to_string(), "0 0.1 3");
+    assert_eq!(component!(Text, y_offset: 0.0000001).to_string(), "yOffset: 0;");
+    assert_eq!(component!(Text, y_offset: 0.1 + 0.2).as_map().get("yOffset").map(Cow::as_ref), Some("0.3"));
+}
+
+#[test]
+fn polyhedron_detail()
+{
+    let geometry = component!(Geometry, primitive: GeometryPrimitive::Icosahedron { radius: 2.0, detail: 0 });
+    assert_eq!(geometry.to_string(), "primitive: icosahedron;radius: 2;detail: 0;");
+    let geometry = component!(Geometry, primitive: GeometryPrimitive::Tetrahedron { radius: 1.0, detail: 3 }, buffer: false);
+    assert_eq!(geometry.as_map().get("buffer").map(Cow::as_ref), Some("false"));
+    assert_eq!(geometry.as_map().get("detail").map(Cow::as_ref), Some("3"));
+}
+
+#[test]
+fn gltf_preload()
+{
+    let assets = Assets::new(0, vec!(Model::gltf("robot", "/models/robot.glb")));
+    assert_eq!
+    (
+        assets.as_raw_html(), 
+        "<a-assets><a-asset-item id=\"robot\" src=\"/models/robot.glb\" response-type=\"arraybuffer\"></a-asset-item></a-assets>"
+    );
+    assert_eq!(component!(GltfModel, src: Selector::id("robot")).to_string(), "#robot");
+    assert_eq!(component!(GltfModel, src: Selector::raw("url(/models/robot.glb)")).to_string(), "url(/models/robot.glb)");
+}
+
+#[test]
+fn testing_helpers()
+{
+    testing::assert_component_serializes(&component!(Shadow, cast: false), "cast: false;");
+    let map = testing::roundtrip_map(&component!(Text, value: Cow::Borrowed("hi"), y_offset: 0.5));
+    assert_eq!(map.get("value").map(Cow::as_ref), Some("hi"));
+    assert_eq!(map.get("negate").map(Cow::as_ref), Some("true"));
+    let map = testing::roundtrip_map(&component!(Geometry, primitive: GeometryPrimitive::Icosahedron { radius: 2.0, detail: 1 }));
+    assert_eq!(map.get("primitive").map(Cow::as_ref), Some("icosahedron"));
+    const PROPS: [(Cow<'static, str>, Cow<'static, str>); 1] = 
+        [(Cow::Borrowed("normalMap"), Cow::Borrowed("url(data:image/png;base64,AAAA)"))];
+    let map = testing::roundtrip_map(&component!(component::Material, props: component::MaterialProps(Cow::Borrowed(&PROPS))));
+    assert_eq!(map.get("normalMap").map(Cow::as_ref), Some("url(data:image/png;base64,AAAA)"));
+}
+
+#[test]
+fn entity_visibility()
+{
+    let mut entity = Entity::new(vec!(Attribute::new("id", "box")), vec!(), vec!());
+    entity.set_visible(false);
+    assert_eq!(entity.as_raw_html(), "<a-entity visible=\"false\" id=\"box\"></a-entity>");
+    entity.set_visible(true);
+    assert_eq!(entity.components().len(), 1);
+    assert_eq!(entity.as_raw_html(), "<a-entity visible=\"true\" id=\"box\"></a-entity>");
+}
+
+#[test]
+fn data_and_aria_attrs()
+{
+    let mut entity = Entity::new(vec!(), vec!(), vec!());
+    assert_eq!(entity.data_attr("track-id", "42"), Ok(None));
+    assert_eq!(entity.data_attr("data-track-id", "43"), Ok(Some(Cow::Borrowed("42"))));
+    assert_eq!(entity.aria_attr("label", "Start button"), Ok(None));
+    assert_eq!(entity.as_raw_html(), "<a-entity data-track-id=\"43\" aria-label=\"Start button\"></a-entity>");
+
+    assert_eq!(entity.data_attr("trackId", "1"), Err(InvalidAttrName("data-trackId".to_owned())));
+    assert_eq!(entity.aria_attr("", "1"), Err(InvalidAttrName("aria-".to_owned())));
+    assert_eq!(entity.data_attr("a b", "1"), Err(InvalidAttrName("data-a b".to_owned())));
+    assert_eq!(entity.attributes().len(), 2);
+}
+
+#[test]
+fn light_constructors()
+{
+    use component::Light;
+
+    assert_eq!(Light::ambient(color::GREY73, 0.2).light_type, LightType::Ambient {});
+    assert_eq!
+    (
+        Light::directional(color::WHITE, 0.1, true).light_type, 
+        LightType::Directional { shadow: OptionalDirectionalShadow::Cast { shadow: DirectionalShadow::DEFAULT } }
+    );
+    assert_eq!
+    (
+        Light::directional(color::WHITE, 1.0, false).to_string(), 
+        Light::DEFAULT.to_string()
+    );
+    let point = Light::point(color::WHITE, 0.5, 50.0);
+    assert_eq!(point.as_map().get("distance").map(Cow::as_ref), Some("50"));
+    assert_eq!(point.as_map().get("castShadow").map(Cow::as_ref), Some("false"));
+    let spot = Light::spot(color::WHITE, 1.0, 45, 0.2, true);
+    assert_eq!(spot.as_map().get("type").map(Cow::as_ref), Some("spot"));
+    assert_eq!(spot.as_map().get("castShadow").map(Cow::as_ref), Some("true"));
+    assert_eq!(component::LocalShadow::DEFAULT.with_map_size(1000, 700).to_string(), "shadowMapWidth: 1024;");
+    let shadow = component::DirectionalShadow::DEFAULT.with_map_size(2048, 3000);
+    assert_eq!((shadow.shadow_map_width, shadow.shadow_map_height), (2048, 2048));
+    let shadow = component::DirectionalShadow::DEFAULT.with_map_size(0, 3072);
+    assert_eq!((shadow.shadow_map_width, shadow.shadow_map_height), (1, 4096));
+    let aimed = Light::spot_at(Selector::id("stage"), color::WHITE, 1.0, 45, 0.2, false);
+    assert_eq!(aimed.as_map().get("target").map(Cow::as_ref), Some("#stage"));
+}
+
+#[test]
+fn portal_shader_schema()
+{
+    let portal = serde_json::to_value(Shader::portal("".into(), "".into())).unwrap();
+    let schema = &portal["schema"];
+    assert_eq!(schema["borderEnabled"], serde_json::json!({ "type": "int", "is": "uniform", "default": 1 }));
+    assert_eq!(schema["pano"], serde_json::json!({ "type": "map", "is": "uniform" }));
+    assert_eq!(schema["backgroundColor"]["default"], "#ff0000");
+    assert_eq!(schema["strokeColor"]["default"], "#ffffff");
+}
+
+#[test]
+fn vector3_constructors()
+{
+    const EYE_LEVEL: Vector3 = Vector3::up().with_y(1.6);
+    assert_eq!(EYE_LEVEL, Vector3 { x: 0.0, y: 1.6, z: 0.0 });
+    assert_eq!(Vector3::forward(), Vector3 { x: 0.0, y: 0.0, z: -1.0 });
+    assert_eq!(RayCaster::DEFAULT.direction, Vector3::forward());
+    assert_eq!(Vector3::splat(2.0).with_x(1.0).with_z(3.0).to_string(), "1 2 3");
+    assert_eq!(Vector3::down().to_string(), "0 -1 0");
+    assert_eq!(Vector3::left().to_string(), "-1 0 0");
+    assert_eq!(Vector3::right().to_string(), "1 0 0");
+    assert_eq!(Vector3::back().to_string(), "0 0 1");
+}
+
+#[test]
+fn vector_serde()
+{
+    let vec3 = Vector3 { x: 1.0, y: -2.5, z: 0.0 };
+    let json = serde_json::to_value(vec3).unwrap();
+    assert_eq!(json, serde_json::json!({ "x": 1.0, "y": -2.5, "z": 0.0 }));
+    assert_eq!(serde_json::from_value::<Vector3>(json).unwrap(), vec3);
+    assert_eq!(serde_json::from_str::<Vector2>(r#"{"x": 1, "y": 2}"#).unwrap(), Vector2 { x: 1.0, y: 2.0 });
+    let vec4 = Vector4 { x: 1.0, y: 2.0, z: 3.0, w: 4.0 };
+    assert_eq!(serde_json::from_str::<Vector4>(&serde_json::to_string(&vec4).unwrap()).unwrap(), vec4);
+
+    // Matches the object form of a `vec3` schema default in Aframe
+    let property = serde_json::to_value(AframeProperty::vec3(Some(vec3))).unwrap();
+    assert_eq!(property, serde_json::json!({ "type": "vec3", "default": { "x": 1.0, "y": -2.5, "z": 0.0 } }));
+    assert_eq!(serde_json::from_value::<Vector3>(property["default"].clone()).unwrap(), vec3);
+}
+
+#[test]
+fn entity_wireframe()
+{
+    let mut entity = Entity::new(vec!(), vec!(), vec!());
+    entity.wireframe(false);
+    assert!(entity.components().is_empty());
+    entity.wireframe(true);
+    assert_eq!(entity.as_raw_html(), "<a-entity material=\"wireframe: true;\"></a-entity>");
+    entity.wireframe(true);
+    assert_eq!(entity.components().len(), 1);
+    entity.wireframe(false);
+    assert_eq!(entity.as_raw_html(), "<a-entity material></a-entity>");
+
+    let mut entity = Entity::with_components(vec!((Cow::Borrowed("material"), Box::new(component!(Material, opacity: 0.5)))));
+    entity.wireframe(true);
+    assert_eq!(entity.as_raw_html(), "<a-entity material=\"opacity: 0.5;wireframe: true;\"></a-entity>");
+}
+
+#[test]
+fn scene_debug()
+{
+    let scene = Scene::with_components(vec!((Cow::Borrowed("embedded"), Box::new(component!(Embedded))))).with_debug();
+    assert_eq!(scene.as_raw_html(), "<a-scene embedded stats debug><a-assets></a-assets></a-scene>");
+    assert_eq!(scene.with_debug().components().len(), 2);
+    assert_eq!(component!(Inspector).to_string(), "");
+}
+
+#[test]
+fn renderer_tone_mapping()
+{
+    assert_eq!(component!(Renderer).to_string(), "");
+    assert_eq!
+    (
+        component!(Renderer, color_management: true, tone_mapping: ToneMapping::ACESFilmic, exposure: 0.8).to_string(), 
+        "colorManagement: true;toneMapping: ACESFilmic;exposure: 0.8;"
+    );
+    assert_eq!(component!(Renderer, anisotropy: 16).to_string(), "anisotropy: 16;");
+}
+
+#[test]
+fn animation_loop()
+{
+    assert_eq!(Animation::DEFAULT.looping, AnimationLoop::none());
+    assert_eq!(AnimationLoop::once(), AnimationLoop::Amount { looping: 1 });
+    assert_eq!(component!(Animation, looping: AnimationLoop::times(3)).to_string(), "loop: 3;");
+    assert_eq!(component!(Animation, looping: AnimationLoop::forever()).to_string(), "loop: true;");
+    assert_eq!(component!(Animation, looping: AnimationLoop::none()).to_string(), "");
+}
+
+#[test]
+fn entity_diff()
+{
+    let old = Entity::with_components(vec!
+    (
+        (Cow::Borrowed("position"), Box::new(component!(Position, x: 1.0))),
+        (Cow::Borrowed("visible"), Box::new(component!(Visible))),
+        (Cow::Borrowed("shadow"), Box::new(component!(Shadow)))
+    ));
+    let new = Entity::with_components(vec!
+    (
+        (Cow::Borrowed("position"), Box::new(component!(Position, x: 2.0))),
+        (Cow::Borrowed("visible"), Box::new(component!(Visible))),
+        (Cow::Borrowed("rotation"), Box::new(component!(Rotation, y: 90.0)))
+    ));
+    assert_eq!
+    (
+        old.diff(&new), 
+        vec!
+        (
+            (Cow::Borrowed("position"), Some("2 0 0".to_owned())),
+            (Cow::Borrowed("rotation"), Some("0 90 0".to_owned())),
+            (Cow::Borrowed("shadow"), None)
+        )
+    );
+    assert!(new.diff(&new.clone()).is_empty());
+}
+
+#[test]
+fn assets_attributes()
+{
+    let plain = assets!(timeout: 0, Image::new("ramen", "/pics/ramen.png"));
+    assert!(plain.as_raw_html().starts_with("<a-assets>"));
+
+    let assets = assets!
+    {
+        timeout: 5000,
+        attributes: [("crossorigin", "anonymous")],
+        Image::new("ramen", "/pics/ramen.png")
+    };
+    assert_eq!
+    (
+        assets.as_raw_html(), 
+        r#"<a-assets timeout="5000" crossorigin="anonymous"><img id="ramen" src="/pics/ramen.png"></img></a-assets>"#
+    );
+    let assets = assets!(attributes: [("crossorigin", "anonymous")], Image::new("ramen", "/pics/ramen.png"));
+    assert_eq!(assets.attributes(), &vec!(Attribute::new("crossorigin", "anonymous")));
+}
+
+#[test]
+fn add_children()
+{
+    let mut scene = Scene::default();
+    scene
+        .add_children(entity::grid(2, 1, Vector3::splat(1.0), |_, _| entity!()))
+        .add_children(Some(entity!()));
+    assert_eq!(scene.children().len(), 3);
+
+    let mut parent = entity!();
+    parent.add_children(vec!(entity!(), entity!())).add_children(std::iter::empty());
+    assert_eq!(parent.children().len(), 2);
+}
+
+#[test]
+fn component_names()
+{
+    use component::{GltfModel, HandTrackingControls, Material, RayCaster};
+    assert_eq!(Position::NAME, "position");
+    assert_eq!(RayCaster::NAME, "raycaster");
+    assert_eq!(GltfModel::NAME, "gltf-model");
+    assert_eq!(HandTrackingControls::NAME, "hand-tracking-controls");
+
+    let entity = entity!
+    {
+        components: (Position::NAME, component!(Position)), (Material::NAME, component!(Material))
+    };
+    assert_eq!(entity.components().iter().map(|(name, _)| name).collect::<Vec<_>>(), vec!("position", "material"));
+}
+
+#[test]
+fn assets_timeout_duration()
+{
+    use std::time::Duration;
+    let image = || Image::new("ramen", "/pics/ramen.png");
+    assert_eq!(Assets::with_timeout(Duration::from_secs(5), vec!(image())), Assets::new(5000, vec!(image())));
+    assert_eq!(assets!(timeout: Duration::from_secs(5), image()), assets!(timeout: 5000, image()));
+    assert_eq!(assets!(timeout: Duration::ZERO, image()).as_raw_html(), assets!(image()).as_raw_html());
+    assert_eq!(Assets::with_timeout(Duration::MAX, vec!()).timeout_ms, u32::MAX);
+}
+
+#[test]
+fn animation_events()
+{
+    let animation = component!(component::Animation, property: Cow::Borrowed("rotation"))
+        .on_events(["click", ""])
+        .pause_on(vec!(String::from("mouseleave")))
+        .resume_on(["mouseenter", "focus"]);
+    assert_eq!(animation.start_events, component::List(Cow::Borrowed(&[Cow::Borrowed("click")])));
+    assert_eq!
+    (
+        animation.to_string(), 
+        "property: rotation;startEvents: click;pauseEvents: mouseleave;resumeEvents: mouseenter,focus;"
+    );
+    assert_eq!(component!(component::Animation).on_events([" "]).to_string(), "");
+}
+
+#[test]
+fn vector_conversions()
+{
+    let vector: Vector3 = (1.0, 2.0, 3.0).into();
+    assert_eq!(vector, Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+    assert_eq!(Vector3::from([1.0, 2.0, 3.0]), vector);
+    assert_eq!(<(f64, f64, f64)>::from(vector), (1.0, 2.0, 3.0));
+    assert_eq!(<[f64; 3]>::from(vector), [1.0, 2.0, 3.0]);
+
+    assert_eq!(Vector2::from((0.5, 1.5)), Vector2 { x: 0.5, y: 1.5 });
+    assert_eq!(<[f64; 2]>::from(Vector2::from([0.5, 1.5])), [0.5, 1.5]);
+    assert_eq!(Vector4::from([1.0, 2.0, 3.0, 4.0]).to_string(), "1 2 3 4");
+    assert_eq!(<(f64, f64, f64, f64)>::from(Vector4::from((1.0, 2.0, 3.0, 4.0))), (1.0, 2.0, 3.0, 4.0));
+}
+
+#[test]
+fn scene_html_document()
+{
+    let scene = scene!
+    {
+        assets: assets!(),
+        components: ("embedded", component!(component::Embedded))
+    };
+    assert_eq!
+    (
+        scene.to_html_document(AFRAME_URL), 
+        format!
+        (
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8"><script src="{}"></script></head><body>{}</body></html>"#, 
+            AFRAME_URL, 
+            scene.as_raw_html()
+        )
+    );
+    assert!(scene.to_html_document("/aframe.js").contains(r#"<body><a-scene embedded"#));
+}
+
+#[test]
+fn colliders()
+{
+    assert_eq!(component!(component::ObbCollider).to_string(), "");
+    assert_eq!(component!(component::ObbCollider, size: 0.5, center_model: true).to_string(), "size: 0.5;centerModel: true;");
+    assert_eq!
+    (
+        component!(component::AabbCollider, objects: Selector::class("obstacle"), interval: 40).to_string(), 
+        "objects: .obstacle;interval: 40;"
+    );
+}
+
+#[test]
+fn string_constructors()
+{
+    use component::{Animation, Link, ObjModel, Sound, Text};
+    let event = String::from("mouseenter");
+    let animation = Animation { start_events: ["click", "focus"].into(), ..Animation::new("position", "0 1 0") };
+    assert_eq!(animation.to_string(), "property: position;to: 0 1 0;startEvents: click,focus;");
+    assert_eq!(Animation { start_events: vec!(event).into(), ..Animation::DEFAULT }.to_string(), "startEvents: mouseenter;");
+    assert_eq!(Link::new("https://aframe.io").to_string(), "href: https://aframe.io;");
+    assert_eq!(ObjModel::new("#tree-obj", "").to_string(), "obj: #tree-obj;");
+    assert_eq!(Sound::new(format!("#{}", "ambient")).src, "#ambient");
+    assert_eq!(Text::new("Hello").to_string(), "value: Hello;");
+
+    let numbers: component::List<u32> = (1..=3).collect();
+    assert_eq!(numbers.to_string(), "1,2,3");
+    assert_eq!(component::List::from(vec!(4, 5)).to_string(), "4,5");
+}
+
+#[test]
+fn background_fill()
+{
+    use component::{Background, BackgroundFill};
+    assert_eq!(component!(Background), Background::color(color::BLACK));
+    assert_eq!(component!(Background).to_string(), "");
+    assert_eq!(Background::color(color::RED).to_string(), "color: #ff0000;");
+    assert_eq!(Background::transparent().to_string(), "transparent: true;");
+    assert_eq!(Background::transparent().fill, BackgroundFill::Transparent {});
+}
+
+#[test]
+fn entity_mixins()
+{
+    let mut entity = entity!();
+    entity.add_mixin("a").add_mixin(String::from("b")).add_mixin("a");
+    assert_eq!(entity.get_attr("mixin"), Some("a b"));
+    assert_eq!(entity.as_raw_html(), r#"<a-entity mixin="a b"></a-entity>"#);
+
+    let mut entity = entity!(attributes: ("mixin", "intersect_ray"));
+    entity.add_mixin("red");
+    assert_eq!(entity.get_attr("mixin"), Some("intersect_ray red"));
+}
+
+#[test]
+fn entity_classes()
+{
+    let mut entity = entity!(attributes: ("id", "button"));
+    entity.add_class("a").add_class("b").add_class("a");
+    assert_eq!(entity.as_raw_html(), r#"<a-entity id="button" class="a b"></a-entity>"#);
+
+    let target = Selector::class("clickable");
+    let mut entity = entity!(attributes: ("class", "interactive"));
+    entity.add_class("clickable");
+    assert_eq!(entity.get_attr("class"), Some("interactive clickable"));
+    assert_eq!(RayCaster::targeting([target]).to_string(), "objects: .clickable;");
+}
+
+#[test]
+fn into_owned()
+{
+    let is_owned = |cow: &Cow<'static, str>| matches!(cow, Cow::Owned(_));
+    let scene = scene!
+    {
+        attributes: ("id", "scene"),
+        assets: assets!(Image::new("ramen", "/pics/ramen.png"), mixin!("red", ("material", component!(component::Material)))),
+        components: ("embedded", component!(component::Embedded)),
+        children: entity!
+        {
+            primitive: "ramen-cube",
+            attributes: ("id", "cube"),
+            components: ("position", component!(Position)),
+            children: entity!(attributes: ("id", "inner"))
+        }
+    };
+    let owned = scene.clone().into_owned();
+    assert_eq!(owned, scene);
+    assert!(owned.attributes().iter().all(|attr| is_owned(&attr.name) && is_owned(&attr.value)));
+    assert!(owned.components().iter().all(|(name, _)| is_owned(name)));
+    assert!(owned.assets().iter().all(|asset| is_owned(asset.id())));
+    assert!(is_owned(owned.assets().get("ramen").unwrap().src()));
+    let cube = &owned.children()[0];
+    assert!(is_owned(&cube.tag()) && is_owned(&cube.attributes()[0].value));
+    assert!(cube.components().iter().all(|(name, _)| is_owned(name)));
+    assert!(is_owned(&cube.children()[0].attributes()[0].value));
+}
+
+#[test]
+fn find_by_id()
+{
+    let mut rig = entity!(attributes: ("id", "rig"));
+    rig.add_children(vec!(entity!(attributes: ("id", "camera")), entity!(attributes: ("id", "hand"), ("class", "first"))));
+    let mut scene = Scene::default();
+    scene.add_children(vec!(rig, entity!(attributes: ("id", "hand"), ("class", "second"))));
+
+    assert_eq!(scene.find("camera").and_then(|entity| entity.get_attr("id")), Some("camera"));
+    assert_eq!(scene.find("hand").and_then(|entity| entity.get_attr("class")), Some("first"));
+    assert!(scene.find("missing").is_none());
+    assert!(scene.children()[0].find("rig").is_some());
+
+    scene.find_mut("camera").unwrap().set_attr("class", "tweaked");
+    assert_eq!(scene.find("camera").and_then(|entity| entity.get_attr("class")), Some("tweaked"));
+}
+
+#[test]
+fn entity_counts()
+{
+    let mut rig = entity!(attributes: ("id", "rig"));
+    let mut hand = entity!(attributes: ("id", "hand"));
+    hand.add_children(vec!(entity!(), entity!()));
+    rig.add_children(vec!(entity!(attributes: ("id", "camera")), hand));
+    assert_eq!(rig.descendant_count(), 4);
+    assert_eq!(rig.max_depth(), 3);
+    assert_eq!(entity!().descendant_count(), 0);
+    assert_eq!(entity!().max_depth(), 1);
+
+    let mut scene = Scene::default();
+    assert_eq!((scene.entity_count(), scene.max_depth()), (0, 0));
+    scene.add_children(vec!(rig, entity!()));
+    assert_eq!(scene.entity_count(), 6);
+    assert_eq!(scene.max_depth(), 3);
+}
+
+#[test]
+fn text_fonts()
+{
+    assert_eq!(component::Text::new("hi").to_string(), "value: hi;");
+    assert_eq!(component!(component::Text, font: component::Font::Exo2Bold).to_string(), "font: exo2bold;");
+    let custom = component!(component::Text, font: component::Font::custom("/fonts/custom-msdf.json"));
+    assert_eq!(custom.as_map().get("font").map(|font| font.as_ref()), Some("/fonts/custom-msdf.json"));
+    assert_eq!(serde_json::to_string(&component::Font::MozillaVr).unwrap(), r#""mozillavr""#);
+}
+
+#[test]
+fn json_export()
+{
+    let mut cube = entity!
+    {
+        attributes: ("id", "cube"), ("class", "clickable"),
+        components: ("position", Position { x: 0.0, y: 1.5, z: -2.0 }), ("visible", component::Visible { visible: false })
+    };
+    cube.add_children(vec!(entity!(primitive: "a-sphere", attributes: ("radius", "0.5"))));
+    assert_eq!
+    (
+        serde_json::to_string(&cube).unwrap(),
+        concat!
+        (
+            r#"{"tag":"a-entity","attributes":{"id":"cube","class":"clickable"},"#,
+            r#""components":{"position":{"x":"0","y":"1.5","z":"-2"},"visible":{"visible":"false"}},"#,
+            r#""children":[{"tag":"a-sphere","attributes":{"radius":"0.5"},"components":{},"children":[]}]}"#
+        )
+    );
+
+    let scene = scene!
+    {
+        attributes: ("id", "scene"),
+        assets: assets!(timeout: 5000, Image::new("sky", "/pics/sky.png")),
+        components: ("embedded", component!(component::Embedded)),
+        children: entity!(attributes: ("id", "empty"))
+    };
+    assert_eq!
+    (
+        serde_json::to_string(&scene).unwrap(),
+        concat!
+        (
+            r#"{"tag":"a-scene","attributes":{"id":"scene"},"components":{"embedded":{}},"#,
+            r#""assets":{"tag":"a-assets","attributes":{"timeout":"5000"},"#,
+            r#""children":[{"tag":"img","attributes":{"id":"sky","src":"/pics/sky.png"},"children":[]}]},"#,
+            r#""children":[{"tag":"a-entity","attributes":{"id":"empty"},"components":{},"children":[]}]}"#
+        )
+    );
+}
+
+#[test]
+fn animation_color_stops()
+{
+    let stops = [(0.0, color::RED), (0.25, color::GREEN), (1.0, color::BLUE)];
+    let chain = component::Animation::color_stops("material.color", &stops, 1000).unwrap();
+    assert_eq!(chain.iter().map(|(name, _)| name.as_ref()).collect::<Vec<_>>(), vec!("animation__material-color-0", "animation__material-color-1"));
+    let (first, second) = (&chain[0].1, &chain[1].1);
+    assert_eq!((first.delay, first.dur, second.delay, second.dur), (0, 250, 250, 750));
+    assert_eq!((first.from.as_ref(), first.to.as_ref()), (color::RED.to_string().as_str(), color::GREEN.to_string().as_str()));
+    assert_eq!((second.from.as_ref(), second.to.as_ref()), (color::GREEN.to_string().as_str(), color::BLUE.to_string().as_str()));
+    assert_eq!(first.property, "material.color");
+
+    let invalid = |stops: &[(f32, color::Rgb)]| component::Animation::color_stops("color", stops, 1000).unwrap_err().to_string();
+    assert_eq!(invalid(&[(0.0, color::RED)]), "Invalid color stops: expected at least 2 stops, found 1");
+    assert_eq!(invalid(&[(0.0, color::RED), (1.5, color::BLUE)]), "Invalid color stops: 1.5 is outside of 0.0..=1.0");
+    assert_eq!(invalid(&[(0.5, color::RED), (0.25, color::BLUE)]), "Invalid color stops: 0.25 comes after 0.5");
+}
+
+#[test]
+fn component_presets()
+{
+    let preset = ComponentPreset::default()
+        .with("material", component!(component::Material, opacity: 0.5))
+        .with("shadow", component!(component::Shadow));
+    let mut entity = entity!
+    {
+        components: ("material", component!(component::Material, opacity: 0.25))
+    };
+    entity.apply_preset(&preset);
+    assert_eq!(entity.components().iter().map(|(name, _)| name.as_ref()).collect::<Vec<_>>(), vec!("material", "shadow"));
+    assert_eq!(entity.components()[0].1.to_string(), "opacity: 0.25;");
+    assert_eq!(entity.as_raw_html(), r#"<a-entity material="opacity: 0.25;" shadow></a-entity>"#);
+
+    entity.apply_preset(&preset);
+    assert_eq!(entity.components().len(), 2);
+}
+
+#[test]
+fn euler_angles()
+{
+    assert_eq!(Vector3::from_euler_degrees(90.0, 0.0, -45.0), Vector3 { x: 90.0, y: 0.0, z: -45.0 });
+    assert_eq!(Vector3::from_euler_radians(std::f64::consts::PI, 0.0, -std::f64::consts::FRAC_PI_2), Vector3 { x: 180.0, y: 0.0, z: -90.0 });
+    let controls = component!(component::ViveControls, orientation_offset: Vector3::from_euler_degrees(0.0, 90.0, 0.0));
+    assert_eq!(controls.to_string(), "orientationOffset: 0 90 0;");
+}
+
+#[test]
+fn declarative_schema()
+{
+    let schema = schema!
+    {
+        updateFreq: number(500.0),
+        "label": string(),
+        enabled: boolean(true)
+    };
+    assert_eq!(schema.len(), 3);
+    assert_eq!(serde_json::to_string(&schema["updateFreq"]).unwrap(), r#"{"type":"number","default":500.0}"#);
+    assert_eq!(serde_json::to_string(&schema["label"]).unwrap(), r#"{"type":"string"}"#);
+    assert_eq!(serde_json::to_string(&schema["enabled"]).unwrap(), r#"{"type":"boolean","default":true}"#);
+}
+
+#[test]
+fn color_strings()
+{
+    let red: Cow<str> = color::RED.into();
+    assert_eq!(red, "#ff0000");
+    let grey: Cow<str> = color::GREY47.into();
+    assert_eq!(grey, "#787878");
+    assert_eq!(serde_json::to_string(&ShaderProperty::color(IsUniform::Yes, Some(color::RED))).unwrap(), r##"{"type":"color","is":"uniform","default":"#ff0000"}"##);
+}
+
+#[test]
+fn map_components()
+{
+    let orbit = component::MapComponent::new([("target", "#target"), ("enableDamping", "true")]);
+    assert_eq!(orbit.to_string(), "target: #target;enableDamping: true;");
+    assert_eq!(orbit.get("enableDamping"), Some("true"));
+    assert_eq!(orbit.as_map().get("target").map(|target| target.as_ref()), Some("#target"));
+    assert_eq!(component::MapComponent::DEFAULT.to_string(), "");
+
+    let entity = entity!(components: ("orbit-controls", Clone::clone(&orbit)));
+    assert_eq!(entity.as_raw_html(), r##"<a-entity orbit-controls="target: #target;enableDamping: true;"></a-entity>"##);
+    let cmp = &entity.components()[0].1;
+    assert!(cmp.eq(&orbit));
+    assert!(!cmp.eq(&component::MapComponent::new([("target", "#other")])));
+    assert_eq!((cmp.as_ref() as &dyn std::any::Any).downcast_ref::<component::MapComponent>(), Some(&orbit));
+}
+
+#[test]
+fn component_separators()
+{
+    assert_eq!(component!(component::Camera, fov: 60.0, near: 0.1).to_string(), "fov: 60;near: 0.1;");
+    assert_eq!(Light::ambient(color::RED, 0.5).to_string(), "type: ambient;color: #ff0000;intensity: 0.5;");
+    assert_eq!(Light::point(color::WHITE, 1.0, 4.0).to_string(), "type: point;decay: 1;distance: 4;castShadow: false;");
+    let props = component::MaterialProps(Cow::Borrowed(&[(Cow::Borrowed("emissive"), Cow::Borrowed("#00ff00"))]));
+    assert_eq!(component!(component::Material, opacity: 0.5, props: props).to_string(), "opacity: 0.5;emissive: #00ff00;");
+}
+
+#[test]
+fn flattened_values_with_separators()
+{
+    const PROPS: [(Cow<'static, str>, Cow<'static, str>); 2] = 
+    [
+        (Cow::Borrowed("normalMap"), Cow::Borrowed("url(data:image/png;base64,AAAA)")),
+        (Cow::Borrowed("envMap"), Cow::Borrowed("https://example.com/env.png"))
+    ];
+    let material = component!(component::Material, props: component::MaterialProps(Cow::Borrowed(&PROPS)));
+    let map = material.as_map();
+    assert_eq!(map.get("normalMap").map(Cow::as_ref), Some("url(data:image/png;base64,AAAA)"));
+    assert_eq!(map.get("envMap").map(Cow::as_ref), Some("https://example.com/env.png"));
+    assert_eq!(material.to_string(), "normalMap: url(data:image/png;base64,AAAA);envMap: https://example.com/env.png;");
+
+    let raw = component::RawComponent(Cow::Borrowed("text: 'a; b'; href: http://example.com"));
+    assert_eq!(raw.as_map().get("text").map(Cow::as_ref), Some("'a; b'"));
+    assert_eq!(raw.as_map().get("href").map(Cow::as_ref), Some("http://example.com"));
+}
+
+#[test]
+fn material_shaders()
+{
+    assert_eq!(component!(component::Material).to_string(), "");
+    assert_eq!(component!(component::Material, shader: component::MaterialShader::Flat).to_string(), "shader: flat;");
+    assert_eq!(component::MaterialShader::Portal.to_string(), shader::PORTAL);
+    let strobe = component!(component::Material, shader: component::MaterialShader::custom("strobe"));
+    assert_eq!(strobe.as_map().get("shader").map(Cow::as_ref), Some("strobe"));
+}
+
+#[test]
+fn promote_attributes()
+{
+    let mut scene = scene!
+    {
+        attributes: ("id", "scene"), ("cursor", "rayOrigin: mouse"), ("my-component", "speed: 2"),
+        assets: assets!(),
+        components: ("embedded", component!(component::Embedded)),
+        children: entity!(attributes: ("id", "box"), ("position", "0 1.5 -2"), ("animation__spin", "property: rotation"))
+    };
+    scene.promote_attributes();
+    assert_eq!(scene.attributes().iter().map(|attr| attr.name.as_ref()).collect::<Vec<_>>(), vec!("id", "my-component"));
+    assert_eq!(scene.components().iter().map(|(name, _)| name.as_ref()).collect::<Vec<_>>(), vec!("embedded", "cursor"));
+    let entity = scene.find("box").unwrap();
+    assert_eq!(entity.attributes().len(), 1);
+    assert!(entity.components()[0].1.eq(&Position { x: 0.0, y: 1.5, z: -2.0 }));
+    assert_eq!(entity.components()[1].0, "animation__spin");
+    assert_eq!(scene.find("box").unwrap().as_raw_html(), r#"<a-entity position="0 1.5 -2" animation__spin="property: rotation" id="box"></a-entity>"#);
+}
+
+#[test]
+fn media_attributes()
+{
+    assert_eq!(Video::new("clip", "/clip.mp4").as_raw_html(), r#"<video id="clip" src="/clip.mp4"></video>"#);
+    assert_eq!(Audio::new("clip", "/clip.mp3").as_raw_html(), r#"<audio id="clip" src="/clip.mp3"></audio>"#);
+    assert_eq!(Video::autoplay("clip", "/clip.mp4").as_raw_html(), r#"<video id="clip" src="/clip.mp4" autoplay="true"></video>"#);
+    assert_eq!(Audio::autoplay("clip", "/clip.mp3").as_raw_html(), r#"<audio id="clip" src="/clip.mp3" autoplay="true"></audio>"#);
+    assert_eq!(Video::preload("clip", "/clip.mp4", Preload::Auto).as_raw_html(), r#"<video id="clip" src="/clip.mp4" preload="auto"></video>"#);
+    assert_eq!(Audio::preload("clip", "/clip.mp3", Preload::Metadata).as_raw_html(), r#"<audio id="clip" src="/clip.mp3" preload="metadata"></audio>"#);
+    assert_eq!
+    (
+        Video::autoplay_muted("pano", "/pano.mp4").as_raw_html(), 
+        r#"<video id="pano" src="/pano.mp4" autoplay="true" muted="true" playsinline="true"></video>"#
+    );
+    let looping = Video::with_options("pano", "/pano.mp4", VideoOptions { looping: true, ..VideoOptions::DEFAULT });
+    assert_eq!(looping.as_raw_html(), r#"<video id="pano" src="/pano.mp4" loop="true"></video>"#);
+    if let Asset::Video(video) = Video::new("clip", "/clip.mp4")
+    {
+        assert_eq!(Vec::<Attribute>::from(&video), video.attributes());
+    }
+}
+
+#[test]
+fn animation_autoplay()
+{
+    let with_autoplay = |autoplay| component!(component::Animation, autoplay: autoplay);
+    assert_eq!(with_autoplay(None).to_string(), "");
+    assert_eq!(with_autoplay(Some(true)).to_string(), "autoplay: true;");
+    assert_eq!(with_autoplay(Some(false)).to_string(), "autoplay: false;");
+    assert_eq!(with_autoplay(None).as_map().get("autoplay").map(|value| value.as_ref()), Some("null"));
+    assert_eq!(with_autoplay(Some(true)).as_map().get("autoplay").map(|value| value.as_ref()), Some("true"));
+    assert_eq!(with_autoplay(Some(false)).as_map().get("autoplay").map(|value| value.as_ref()), Some("false"));
+}
+
+#[wasm_bindgen_test]
+async fn test_apply_to_dom()
+{
+    init_aframe_tests().await;
+
+    let body = web_sys::window().and_then(|win| win.document()).unwrap().body().unwrap();
+    let ids = ["batched", "individual"];
+    for id in ids
+    {
+        let entity = entity!(attributes: ("id", id));
+        body.append_with_node_1(entity.as_element().unwrap().as_ref()).unwrap();
+    }
+    let components: Vec<(Cow<'static, str>, Box<dyn Component>)> = components_vec!
+    {
+        ("position", component::Position { x: 1.0, y: 2.0, z: 3.0 }),
+        ("material", component!(component::Material, opacity: 0.5, wireframe: true)),
+        ("visible", component::Visible { visible: false }),
+        ("shadow", component!(component::Shadow, receive: false))
+    };
+    const ROUNDS: usize = 100;
+    let now = || js_sys::Date::now();
+
+    let start = now();
+    for _ in 0..ROUNDS
+    {
+        Entity::apply_to_dom("batched", &components).unwrap();
+    }
+    let batched = now() - start;
+
+    let start = now();
+    let element = sys::query("#individual").unwrap();
+    for _ in 0..ROUNDS
+    {
+        for (name, cmp) in components.iter()
+        {
+            element.set_attribute(name, &cmp.to_string()).unwrap();
+        }
+    }
+    let individual = now() - start;
+    console_log!("apply_to_dom: {}ms batched vs {}ms individually for {} rounds", batched, individual, ROUNDS);
+
+    let batched = sys::query("#batched").unwrap();
+    assert_eq!(batched.get_attribute("visible").as_deref(), Some("false"));
+}
+
+#[test]
+fn material_src()
+{
+    use component::{Material, MaterialSrc};
+    let assets = assets!(Image::new("ramen", "/pics/ramen.png"));
+    let image = assets.get("ramen").unwrap();
+    let material = component!(Material, src: image.into());
+    assert_eq!(material.src, MaterialSrc::Asset(Selector::id("ramen")));
+    assert_eq!(material.to_string(), "src: #ramen;");
+    assert_eq!(material.as_map().get("src").map(|src| src.as_ref()), Some("#ramen"));
+    if let Asset::Image(image) = image
+    {
+        assert_eq!(MaterialSrc::from(image), material.src);
+    }
+
+    assert_eq!(component!(Material, src: MaterialSrc::url("/pics/noise.bmp")).to_string(), "src: url(/pics/noise.bmp);");
+    assert_eq!(component!(Material).to_string(), "");
+}
+
+#[test]
+fn lowercase_booleans()
+{
+    macro_rules! defaults
+    {
+        ($($cmp:ident),*) => { vec!($((component::$cmp::NAME, Box::new(component::$cmp::DEFAULT) as Box<dyn Component>)),*) }
+    }
+    let components = defaults!
+    (
+        Animation, Background, Camera, Cursor, DaydreamControls, DeviceOrientationPermissionUI, Embedded, Fog, 
+        GearVRControls, Geometry, GltfModel, HandControls, HandTrackingControls, LaserControls, Inspector, 
+        KeyboardShortcuts, Layer, Light, Line, Link, LoadingScreen, LookControls, MagicLeapControls, Material, 
+        ObbCollider, ObjModel, OculusGoControls, OculusTouchControls, Pool, Position, Renderer, RayCaster, Rotation, 
+        Scale, Screenshot, Shadow, Sound, Stats, Text, TrackedControls, Visible, ViveControls, ViveFocusControls, 
+        VrModeUi, WasdControls, WebXR, WindowsMotionControls, LookAt, AabbCollider
+    );
+    let is_bool_like = |value: &str| value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false");
+    for (name, cmp) in components
+    {
+        for (property, value) in cmp.as_map()
+        {
+            assert!
+            (
+                !is_bool_like(&value) || value == "true" || value == "false", 
+                "{}.{} serializes as {}", name, property, value
+            );
+        }
+        let display = cmp.to_string();
+        for value in display.split(';').filter_map(|prop| prop.split_once(':')).map(|(_, value)| value.trim())
+        {
+            assert!(!is_bool_like(value) || value == "true" || value == "false", "{} displays as {}", name, display);
+        }
+    }
+    assert_eq!(component!(component::Renderer, alpha: false).as_map().get("alpha").map(|alpha| alpha.as_ref()), Some("false"));
+    assert_eq!(component!(component::Renderer, alpha: false).to_string(), "alpha: false;");
+}
+
+#[test]
+fn chained_construction()
+{
+    let entity = entity!()
+        .with_attribute("id", "box")
+        .with_component(Position::NAME, Position { x: 0.0, y: 1.0, z: 0.0 })
+        .with_component(component::Visible::NAME, component::Visible { visible: false });
+    assert_eq!(entity.get_attr("id"), Some("box"));
+    assert_eq!(entity.as_raw_html(), r#"<a-entity position="0 1 0" visible="false" id="box"></a-entity>"#);
+
+    let scene = Scene::default()
+        .with_attribute("id", "scene")
+        .with_component(component::Embedded::NAME, component!(component::Embedded));
+    assert_eq!(scene.get_attr("id"), Some("scene"));
+    assert_eq!(scene.components().len(), 1);
+}
+
+#[test]
+fn scene_from_html()
+{
+    fn component<'a, T: 'static>(entity: &'a Entity, name: &str) -> Option<&'a T>
+    {
+        entity.components().iter()
+            .find(|(cmp_name, _)| cmp_name == name)
+            .and_then(|(_, cmp)| (cmp.as_ref() as &dyn std::any::Any).downcast_ref::<T>())
+    }
+    let scene = scene!
+    {
+        attributes: ("id", "imported"),
+        assets: assets!
+        {
+            timeout: 5000,
+            Image::new("ramen", "/pics/ramen.png"),
+            Video::autoplay_muted("clip", "/video/clip.mp4"),
+            Model::gltf("robot", "/models/robot.glb"),
+            mixin!("red", ("material", component!(component::Material, opacity: 0.5)))
+        },
+        components: ("embedded", component!(component::Embedded)),
+        children: 
+            entity!
+            {
+                attributes: ("id", "cube"), ("mixin", "red"),
+                components: ("position", Position { x: 0.0, y: 1.5, z: -2.0 }), ("visible", component::Visible { visible: false })
+            },
+            entity!
+            {
+                primitive: "ramen-cube",
+                attributes: ("color", "#ff0000"),
+                components: ("rotation", Rotation { x: 0.0, y: 45.0, z: 0.0 })
+            }
+    };
+    let html = scene.as_raw_html();
+    let imported = Scene::from_html(&html).unwrap();
+    assert_eq!(imported.as_raw_html(), html);
+    assert_eq!(imported.find("cube").unwrap().components()[0].1.to_string(), "0 1.5 -2");
+    assert!(component::<Position>(imported.find("cube").unwrap(), "position").is_some());
+    assert_eq!(imported.children()[1].get_attr("color"), Some("#ff0000"));
+
+    let authored = r##"
+        <!DOCTYPE html>
+        <html>
+            <body>
+                <!-- A hand-written scene -->
+                <a-scene fog='type: exponential' embedded>
+                    <a-assets><img id=sky src="/pics/sky.png"></a-assets>
+                    <a-entity id="rig" position="0 1.6 0" my-component="speed: 2">
+                        <a-entity id="camera" camera look-controls="enabled: false"/>
+                    </a-entity>
+                    <a-sky src="#sky" visible="true"></a-sky>
+                </a-scene>
+            </body>
+        </html>"##;
+    let imported = Scene::from_html(authored).unwrap();
+    assert_eq!(imported.components().iter().map(|(name, _)| name).collect::<Vec<_>>(), vec!("fog", "embedded"));
+    assert_eq!(imported.assets().get("sky"), Some(&Image::new("sky", "/pics/sky.png")));
+    let rig = imported.find("rig").unwrap();
+    assert_eq!(component::<component::RawComponent>(rig, "my-component").map(|raw| raw.0.as_ref()), Some("speed: 2"));
+    assert_eq!(component::<component::RawComponent>(rig, "my-component").unwrap().as_map().get("speed").map(|v| v.as_ref()), Some("2"));
+    assert!(imported.find("camera").is_some());
+    let sky = &imported.children()[1];
+    assert_eq!(sky.tag(), "a-sky");
+    assert_eq!(sky.get_attr("src"), Some("#sky"));
+    assert!(component::<component::Visible>(sky, "visible").is_some());
+
+    assert_eq!(Scene::from_html("<a-entity></a-entity>").unwrap_err().message, "No <a-scene> element found");
+    assert_eq!(Scene::from_html("<a-scene><a-entity></a-scene>").unwrap_err().to_string(), "Unexpected closing tag </a-scene> at byte 19");
+    assert_eq!(Scene::from_html("<a-scene><a-entity>").unwrap_err().to_string(), "Unclosed <a-entity> at byte 9");
+    assert_eq!(Scene::from_html("<a-scene><div></div></a-scene>").unwrap_err().offset, 9);
+}
+
+#[test]
+fn scene_from_html_raw_text()
+{
+    let document = r##"
+        <html>
+            <head>
+                <style>a-scene > a-entity { display: none; }</style>
+                <script>
+                    if (a < b && "</div>".length > 0) { document.body.innerHTML = "<a-scene></a-scene>"; }
+                </SCRIPT>
+            </head>
+            <body>
+                <a-scene><a-entity id="box"></a-entity></a-scene>
+            </body>
+        </html>"##;
+    let imported = Scene::from_html(document).unwrap();
+    assert!(imported.find("box").is_some());
+    assert_eq!(Scene::from_html("<script>if (a < b) {}").unwrap_err().to_string(), "Unclosed <script> at byte 0");
+}
+
+#[test]
+fn vr_mode_ui_buttons()
+{
+    assert_eq!(component!(component::VrModeUi).to_string(), "");
+    let buttons = component::VrModeUi::with_custom_buttons(Selector::id("enter-vr"), Selector::class("enter-ar"));
+    assert_eq!(buttons.to_string(), "enterVRButton: #enter-vr;enterARButton: .enter-ar;");
+}
+
+#[test]
+fn const_components()
+{
+    const AMBIENT: component::Sound = component!(component::Sound, src: Cow::Borrowed("#ambient"), autoplay: true);
+    static GRAB_CURSOR: component::Cursor = component!
+    (
+        component::Cursor, 
+        down_events: component::List(Cow::Borrowed(&[Cow::Borrowed("grab")])), 
+        fuse: true
+    );
+    assert_eq!(AMBIENT.to_string(), "src: #ambient;autoplay: true;");
+    assert_eq!(GRAB_CURSOR.to_string(), "downEvents: grab;fuse: true;");
+}
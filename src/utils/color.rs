@@ -22,6 +22,8 @@ macro_rules! def_color
                 Self { $($field),* }
             }
         }
+        /// Formats the color as lowercase hex, e.g. `#ff0000`, which Aframe 
+        /// parses reliably wherever a color is expected.
         impl std::fmt::Display for $name
         {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
@@ -31,6 +33,7 @@ macro_rules! def_color
                 Ok(())
             }
         }
+        /// The hex form of the color, as given by its `Display` implementation
         impl From<$name> for Cow<'_, str>
         {
             fn from(color: $name) -> Self 
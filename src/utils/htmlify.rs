@@ -4,9 +4,229 @@ use std::borrow::Cow;
 use crate::{Asset, AssetItem, Assets, Audio, Entity, Image, Mixin, Scene, Video};
 use htmlify::*;
 
+/// Extension of [Htmlify] which renders an indented, multi-line version of the
+/// HTML. Useful for debugging or diffing generated scenes. Implemented for 
+/// everything that implements [Htmlify].
+pub trait PrettyHtmlify: Htmlify
+{
+    /// Convert this to a string of HTML, with nested elements placed on their
+    /// own line and indented by `indent` spaces per level.
+    fn as_pretty_html(&self, indent: usize) -> String;
+}
+
+impl<T: Htmlify + ?Sized> PrettyHtmlify for T
+{
+    fn as_pretty_html(&self, indent: usize) -> String 
+    {
+        let mut html = String::new();
+        write_pretty_html(self, indent, 0, &mut html);
+        html
+    }
+}
+
+/// Wrapper for [Attribute] which adds `Hash` and `Ord` implementations, 
+/// ordering by name and then by value. [Attribute] is defined in the `htmlify`
+/// crate, so these can't be implemented on it directly. This allows attributes
+/// to be placed in a `HashSet` or `BTreeSet`/`BTreeMap`, for example to dedupe
+/// conflicting attributes before rendering.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct OrdAttribute(pub Attribute);
+
+impl std::hash::Hash for OrdAttribute
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) 
+    {
+        self.0.name.hash(state);
+        self.0.value.hash(state);
+    }
+}
+
+impl PartialOrd for OrdAttribute
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> 
+    {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdAttribute
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering 
+    {
+        self.0.name.cmp(&other.0.name).then_with(|| self.0.value.cmp(&other.0.value))
+    }
+}
+
+impl std::ops::Deref for OrdAttribute
+{
+    type Target = Attribute;
+    fn deref(&self) -> &Self::Target
+    {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for OrdAttribute
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        self.0.fmt(f)
+    }
+}
+
+impl From<Attribute> for OrdAttribute
+{
+    fn from(attribute: Attribute) -> Self 
+    {
+        Self(attribute)
+    }
+}
+
+impl From<OrdAttribute> for Attribute
+{
+    fn from(attribute: OrdAttribute) -> Self 
+    {
+        attribute.0
+    }
+}
+
+/// Replace the value of the first attribute with the same name, removing any 
+/// later duplicates, or push the attribute if none exists. Returns the replaced
+/// value.
+pub(crate) fn set_attr(attributes: &mut Vec<Attribute>, attribute: Attribute) -> Option<Cow<'static, str>>
+{
+    match attributes.iter().position(|attr| attr.name == attribute.name)
+    {
+        Some(i) =>
+        {
+            let mut index = 0;
+            attributes.retain(|attr| 
+            {
+                index += 1;
+                index <= i + 1 || attr.name != attribute.name
+            });
+            Some(std::mem::replace(&mut attributes[i].value, attribute.value))
+        },
+        None =>
+        {
+            attributes.push(attribute);
+            None
+        }
+    }
+}
+
+/// Returned when an attribute name contains characters which aren't allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidAttrName(pub String);
+
+impl std::fmt::Display for InvalidAttrName
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        write!(f, "Invalid attribute name: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidAttrName {}
+
+/// Prefixes `key` with `prefix` unless it is already prefixed. The rest of the
+/// name must be non-empty and may only contain lowercase ASCII letters, digits,
+/// `-`, `_` and `.`.
+pub(crate) fn prefixed_attr_name(prefix: &str, key: &str) -> Result<String, InvalidAttrName>
+{
+    let key = key.strip_prefix(prefix).unwrap_or(key);
+    let valid = !key.is_empty() && key.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.'));
+    if valid
+    {
+        Ok(format!("{}{}", prefix, key))
+    }
+    else
+    {
+        Err(InvalidAttrName(format!("{}{}", prefix, key)))
+    }
+}
+
+/// Get the value of the first attribute with the given name
+pub(crate) fn get_attr<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a str>
+{
+    attributes.iter()
+        .find(|attr| attr.name == name)
+        .map(|attr| attr.value.as_ref())
+}
+
+/// Builds the opening tag for an element, only separating the tag name and the 
+/// attributes with a space when there are attributes to write.
+pub(crate) fn open_tag(item: &(impl Htmlify + ?Sized)) -> String
+{
+    let attributes = item.attributes()
+        .iter()
+        .map(Attribute::to_string)
+        .collect::<Vec<String>>()
+        .join(" ");
+    if attributes.is_empty()
+    {
+        format!("<{}>", item.tag())
+    }
+    else
+    {
+        format!("<{} {}>", item.tag(), attributes)
+    }
+}
+
+/// Compact HTML output, with no padding around the attributes or inner HTML.
+pub(crate) fn raw_html(item: &(impl Htmlify + ?Sized)) -> String
+{
+    format!("{}{}</{}>", open_tag(item), item.inner_html_as_string(), item.tag())
+}
+
+/// Builds an element from borrowed inner items, rather than the owned items 
+/// returned by [Htmlify::inner_html], so that rendering a tree doesn't clone it.
+#[cfg(feature = "web")]
+fn element<'a>(item: &impl Htmlify, inner: impl Iterator<Item = &'a dyn Htmlify>) -> Option<web_sys::Element>
+{
+    let document = web_sys::window().and_then(|win| win.document())?;
+    let element = document.create_element(&item.tag()).ok()?;
+    for attribute in item.attributes()
+    {
+        element.set_attribute(&attribute.name, &attribute.value).ok()?;
+    }
+    for inner in inner
+    {
+        element.append_with_node_1(inner.as_element()?.as_ref()).ok()?;
+    }
+    Some(element)
+}
+
+fn write_pretty_html(item: &(impl Htmlify + ?Sized), indent: usize, depth: usize, html: &mut String)
+{
+    let padding = " ".repeat(indent * depth);
+    if item.tag() == "__STRING_MARKER"
+    {
+        html.push_str(&padding);
+        html.push_str(&item.as_raw_html());
+        html.push('\n');
+        return;
+    }
+    let inner = item.inner_html();
+    html.push_str(&padding);
+    html.push_str(&open_tag(item));
+    if !inner.is_empty()
+    {
+        html.push('\n');
+        for child in inner.iter()
+        {
+            write_pretty_html(&**child, indent, depth + 1, html);
+        }
+        html.push_str(&padding);
+    }
+    html.push_str(&format!("</{}>\n", item.tag()));
+}
+
 impl Htmlify for Scene
 {
     fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("a-scene") }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
         self.components().iter()
@@ -20,32 +240,35 @@ impl Htmlify for Scene
             .chain(self.children().iter().map(|child| Box::new(child.clone()) as Box<dyn Htmlify>))
             .collect()
     }
+    fn inner_html_as_string(&self) -> String
+    {
+        std::iter::once(self.assets().as_raw_html())
+            .chain(self.children().iter().map(Htmlify::as_raw_html))
+            .collect()
+    }
+    #[cfg(feature = "web")]
+    fn as_element(&self) -> Option<web_sys::Element>
+    {
+        element
+        (
+            self, 
+            std::iter::once(self.assets() as &dyn Htmlify)
+                .chain(self.children().iter().map(|child| child as &dyn Htmlify))
+        )
+    }
 }
 
 impl Htmlify for Entity
 {
-    fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("a-entity") }
+    fn tag(&self) -> Cow<'static, str> { Entity::tag(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
-        self.components().iter()
-            .map(crate::component::cmp_to_attr)
-            .chain(self.attributes().iter().map(Attribute::clone))
+        self.component_attributes().iter()
+            .chain(self.attributes().iter())
+            .cloned()
             .collect()
     }
-    fn as_raw_html(&self) -> String 
-    {
-        format!
-        (
-            "<{0} {2}> {1} </{0}>",
-            self.tag(),
-            self.inner_html_as_string(),
-            Htmlify::attributes(self)
-                .iter()
-                .map(Attribute::to_string)
-                .collect::<Vec<String>>()
-                .join(" ")
-        )
-    }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn inner_html(&self) -> Vec<Box<dyn Htmlify>>
     {
         self.children()
@@ -53,26 +276,39 @@ impl Htmlify for Entity
             .map(|child| Box::new(child.clone()) as Box<dyn Htmlify>)
             .collect()
     }
+    fn inner_html_as_string(&self) -> String
+    {
+        self.children().iter().map(Htmlify::as_raw_html).collect()
+    }
+    #[cfg(feature = "web")]
+    fn as_element(&self) -> Option<web_sys::Element>
+    {
+        element(self, self.children().iter().map(|child| child as &dyn Htmlify))
+    }
 }
 
 impl Htmlify for Assets
 {
     fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("a-assets") }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
-        if self.timeout_ms <= 0
-        {
-            vec!()
-        }
-        else
-        {
-            vec!(Attribute::new("timeout", self.timeout_ms.to_string()))
-        }
+        let timeout = (self.timeout_ms != 0).then(|| Attribute::new("timeout", self.timeout_ms.to_string()));
+        timeout.into_iter().chain(self.attributes.iter().cloned()).collect()
     }
     fn inner_html(&self) -> Vec<Box<dyn Htmlify>>
     {
         self.assets.iter().map(|asset| Box::new(asset.clone()) as Box<dyn Htmlify>).collect()
     }
+    fn inner_html_as_string(&self) -> String
+    {
+        self.assets.iter().map(Htmlify::as_raw_html).collect()
+    }
+    #[cfg(feature = "web")]
+    fn as_element(&self) -> Option<web_sys::Element>
+    {
+        element(self, self.assets.iter().map(|asset| asset as &dyn Htmlify))
+    }
 }
 
 impl Htmlify for Asset
@@ -92,25 +328,13 @@ impl Htmlify for Asset
     {
         self.into()
     }
-    fn as_raw_html(&self) -> String 
-    {
-        format!
-        (
-            "<{0} {2}> {1} </{0}>",
-            self.tag(),
-            self.inner_html_as_string(),
-            self.attributes()
-                .iter()
-                .map(Attribute::to_string)
-                .collect::<Vec<String>>()
-                .join(" ")
-        )
-    }
+    fn as_raw_html(&self) -> String { raw_html(self) }
 }
 
 impl Htmlify for AssetItem
 {
     fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("a-asset-item") }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
         vec!
@@ -124,6 +348,7 @@ impl Htmlify for AssetItem
 impl Htmlify for Image
 {
     fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("img") }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
         vec!
@@ -137,44 +362,27 @@ impl Htmlify for Image
 impl Htmlify for Video
 {
     fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("video") }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
-        let mut attrs = vec!
-        (
-            Attribute::new("id", self.id.clone()), 
-            Attribute::new("src", self.src.clone()), 
-            Attribute::new("preload", self.preload.to_string())
-        );
-        if self.autoplay
-        {
-            attrs.push(Attribute::new("autoplay", "true"))
-        }
-        attrs
+        self.into()
     }
 }
 
 impl Htmlify for Audio
 {
     fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("audio") }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
-        let mut attrs = vec!
-        (
-            Attribute::new("id", self.id.clone()), 
-            Attribute::new("src", self.src.clone()), 
-            Attribute::new("preload", self.preload.to_string())
-        );
-        if self.autoplay
-        {
-            attrs.push(Attribute::new("autoplay", "true"))
-        }
-        attrs
+        self.into()
     }
 }
 
 impl Htmlify for Mixin
 {
     fn tag(&self) -> Cow<'static, str> { Cow::Borrowed("a-mixin") }
+    fn as_raw_html(&self) -> String { raw_html(self) }
     fn attributes(&self) -> Vec<Attribute>
     {
         self.components.iter()
@@ -2,13 +2,19 @@
 
 pub mod color;
 pub mod htmlify;
+pub(crate) mod parse;
 
 pub use ::htmlify::*;
+pub use self::htmlify::{InvalidAttrName, OrdAttribute, PrettyHtmlify};
+pub use self::parse::ParseError;
 pub use const_default::ConstDefault;
+#[cfg(feature = "web")]
 use js_sys::{Object, Reflect};
+#[cfg(feature = "web")]
 use wasm_bindgen::{JsCast, JsValue};
 use std::{borrow::Cow, fmt::Display};
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
+#[cfg(feature = "web")]
 pub use js_sys::Function;
 
 /// Allows a javascript function to be defined inline. Accepts 2 forms of syntax:
@@ -18,6 +24,7 @@ pub use js_sys::Function;
 /// - `===` and `!==` cannot be parsed correctly, use `==` and `!=` instead.
 /// - String literals must be double-quoted, not single-quoted.
 /// - Statements missing a terminating a semi-colon may not parse correctly.
+#[cfg(feature = "web")]
 #[macro_export]
 macro_rules! js
 {
@@ -31,8 +38,45 @@ macro_rules! js
     }
 }
 
-/// A 2-dimensional vector
-#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+/// Builds a javascript function which attaches an event listener to the element
+/// of a component, for use as the `init` of a [`component_def!`](crate::component_def). 
+/// Accepts 2 forms of syntax:
+/// `add_event_listener!(this, "click", <js code>)`
+/// `add_event_listener!(this, "click", evt =>> <js code>)`
+/// The listener body is an arrow function, so `this` still refers to the 
+/// component, and the event is available as `evt` unless another name is given.
+/// The same limitations as [`js!`](crate::js) apply:
+/// - `===` and `!==` cannot be parsed correctly, use `==` and `!=` instead.
+/// - String literals must be double-quoted, not single-quoted.
+/// - Statements missing a terminating a semi-colon may not parse correctly.
+/// ```ignore
+/// component_def!
+/// {
+///     init: add_event_listener!(this, "click", this.el.setAttribute("visible", false);),
+/// }
+/// ```
+#[cfg(feature = "web")]
+#[macro_export]
+macro_rules! add_event_listener
+{
+    ($this:ident, $event:literal, $arg:ident =>> $($tt:tt)*) => 
+    {
+        $crate::utils::Function::new_no_args(concat!
+        (
+            stringify!($this), ".el.addEventListener(", stringify!($event), ", (", stringify!($arg), ") => { ", 
+            stringify!($($tt)*), 
+            " });"
+        ))
+    };
+    ($this:ident, $event:literal, $($tt:tt)*) => 
+    {
+        $crate::add_event_listener!($this, $event, evt =>> $($tt)*)
+    }
+}
+
+/// A 2-dimensional vector. Serializes to and from the `{x, y}` object form 
+/// used by Aframe schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Vector2
 {
     pub x: f64,
@@ -43,8 +87,9 @@ impl ConstDefault for Vector2
     const DEFAULT: Vector2 = Vector2 { x: 0.0, y: 0.0 };
 }
 
-/// A 3-dimensional vector
-#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+/// A 3-dimensional vector. Serializes to and from the `{x, y, z}` object form
+/// used by Aframe schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Vector3
 {
     pub x: f64,
@@ -55,9 +100,85 @@ impl ConstDefault for Vector3
 {
     const DEFAULT: Vector3 = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
 }
+impl Vector3
+{
+    /// `(0, 1, 0)`
+    pub const fn up() -> Self
+    {
+        Self { x: 0.0, y: 1.0, z: 0.0 }
+    }
+
+    /// `(0, -1, 0)`
+    pub const fn down() -> Self
+    {
+        Self { x: 0.0, y: -1.0, z: 0.0 }
+    }
+
+    /// `(0, 0, -1)`. Cameras and raycasters look down the negative z axis in 
+    /// Aframe.
+    pub const fn forward() -> Self
+    {
+        Self { x: 0.0, y: 0.0, z: -1.0 }
+    }
+
+    /// `(0, 0, 1)`
+    pub const fn back() -> Self
+    {
+        Self { x: 0.0, y: 0.0, z: 1.0 }
+    }
+
+    /// `(1, 0, 0)`
+    pub const fn right() -> Self
+    {
+        Self { x: 1.0, y: 0.0, z: 0.0 }
+    }
+
+    /// `(-1, 0, 0)`
+    pub const fn left() -> Self
+    {
+        Self { x: -1.0, y: 0.0, z: 0.0 }
+    }
+
+    /// Euler angles in degrees, as expected by the `orientation_offset` of the
+    /// controller components. This is the same as building the vector 
+    /// directly, but makes the unit explicit at the call site. Use 
+    /// [`Vector3::from_euler_radians`] for angles in radians.
+    pub const fn from_euler_degrees(x: f64, y: f64, z: f64) -> Self
+    {
+        Self { x, y, z }
+    }
+
+    /// Euler angles given in radians, converted to the degrees Aframe expects
+    pub fn from_euler_radians(x: f64, y: f64, z: f64) -> Self
+    {
+        Self { x: x.to_degrees(), y: y.to_degrees(), z: z.to_degrees() }
+    }
+
+    /// A vector with every component set to `v`
+    pub const fn splat(v: f64) -> Self
+    {
+        Self { x: v, y: v, z: v }
+    }
+
+    pub const fn with_x(self, x: f64) -> Self
+    {
+        Self { x, ..self }
+    }
+
+    pub const fn with_y(self, y: f64) -> Self
+    {
+        Self { y, ..self }
+    }
 
-/// A 4-dimensional vector
-#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+    pub const fn with_z(self, z: f64) -> Self
+    {
+        Self { z, ..self }
+    }
+}
+
+/// A 4-dimensional vector. Serializes to and from the `{x, y, z, w}` object 
+/// form used by Aframe schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Vector4
 {
     pub x: f64,
@@ -70,11 +191,48 @@ impl ConstDefault for Vector4
     const DEFAULT: Vector4 = Vector4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
 }
 
+/// Converts a borrowed string into an owned one. Already-owned strings are 
+/// moved rather than copied.
+pub(crate) fn owned_str(cow: Cow<'static, str>) -> Cow<'static, str>
+{
+    Cow::Owned(cow.into_owned())
+}
+
+/// Converts the names and values of the given attributes into owned strings
+pub(crate) fn owned_attrs(attributes: Vec<Attribute>) -> Vec<Attribute>
+{
+    attributes.into_iter()
+        .map(|attr| Attribute { name: owned_str(attr.name), value: owned_str(attr.value) })
+        .collect()
+}
+
+/// Number of decimal places used when formatting floats for Aframe.
+pub const FLOAT_PRECISION: usize = 6;
+
+/// Formats a float the way Aframe expects to parse it: with at most 
+/// `FLOAT_PRECISION` decimal places, without trailing zeroes and never in 
+/// scientific notation. Magnitudes too small to be represented at that 
+/// precision are clamped to `0`.
+pub fn fmt_float(value: f64) -> String
+{
+    if !value.is_finite()
+    {
+        return value.to_string();
+    }
+    let formatted = format!("{:.*}", FLOAT_PRECISION, value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed
+    {
+        "-0" | "" => "0".to_owned(),
+        _ => trimmed.to_owned()
+    }
+}
+
 impl Display for Vector2
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
     {
-        write!(f, "{} {}", self.x, self.y)
+        write!(f, "{} {}", fmt_float(self.x), fmt_float(self.y))
     }
 }
 
@@ -82,7 +240,7 @@ impl Display for Vector3
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
     {
-        write!(f, "{} {} {}", self.x, self.y, self.z)
+        write!(f, "{} {} {}", fmt_float(self.x), fmt_float(self.y), fmt_float(self.z))
     }
 }
 
@@ -90,11 +248,151 @@ impl Display for Vector4
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
     {
-        write!(f, "{} {} {} {}", self.x, self.y, self.z, self.w)
+        write!(f, "{} {} {} {}", fmt_float(self.x), fmt_float(self.y), fmt_float(self.z), fmt_float(self.w))
+    }
+}
+
+/// Implements conversions between a vector and the tuple and array of its 
+/// components, in both directions.
+macro_rules! vector_conversions
+{
+    ($name:ident, $n:literal, $($field:ident: $ty:ty),*) => 
+    {
+        impl From<($($ty),*)> for $name
+        {
+            fn from(($($field),*): ($($ty),*)) -> Self
+            {
+                Self { $($field),* }
+            }
+        }
+        impl From<[f64; $n]> for $name
+        {
+            fn from([$($field),*]: [f64; $n]) -> Self
+            {
+                Self { $($field),* }
+            }
+        }
+        impl From<$name> for ($($ty),*)
+        {
+            fn from(vector: $name) -> Self
+            {
+                ($(vector.$field),*)
+            }
+        }
+        impl From<$name> for [f64; $n]
+        {
+            fn from(vector: $name) -> Self
+            {
+                [$(vector.$field),*]
+            }
+        }
+    }
+}
+vector_conversions!(Vector2, 2, x: f64, y: f64);
+vector_conversions!(Vector3, 3, x: f64, y: f64, z: f64);
+vector_conversions!(Vector4, 4, x: f64, y: f64, z: f64, w: f64);
+
+/// A CSS selector, for use in selector-typed component fields. Prefer the `id`
+/// and `class` constructors, which add the leading `#` or `.` when displayed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector
+{
+    /// Displays as `#{id}`
+    Id(Cow<'static, str>),
+    /// Displays as `.{class}`
+    Class(Cow<'static, str>),
+    /// Displays as-is
+    Raw(Cow<'static, str>)
+}
+
+impl Selector
+{
+    /// Selects the element with the given id (no leading `#`)
+    pub fn id(id: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self::Id(id.into())
+    }
+
+    /// Selects elements with the given class (no leading `.`)
+    pub fn class(class: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self::Class(class.into())
+    }
+
+    /// Any other selector, used exactly as given
+    pub fn raw(selector: impl Into<Cow<'static, str>>) -> Self
+    {
+        Self::Raw(selector.into())
+    }
+}
+
+impl Display for Selector
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        match self
+        {
+            Self::Id(id) => write!(f, "#{}", id),
+            Self::Class(class) => write!(f, ".{}", class),
+            Self::Raw(selector) => write!(f, "{}", selector)
+        }
+    }
+}
+
+impl Serialize for Selector
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Serializes a list of attributes as a map from name to value, in order
+pub(crate) struct AttributeMap<'a>(pub &'a [Attribute]);
+
+impl Serialize for AttributeMap<'_>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_map(self.0.iter().map(|attr| (&attr.name, &attr.value)))
+    }
+}
+
+/// Serializes a list of components as a map from name to properties, in order.
+/// The properties of each component are sorted by name so that the output is 
+/// stable.
+pub(crate) struct ComponentMap<'a>(pub &'a [(Cow<'static, str>, Box<dyn crate::component::Component>)]);
+
+impl Serialize for ComponentMap<'_>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_map
+        (
+            self.0.iter().map(|(name, cmp)| (name, cmp.as_map().into_iter().collect::<std::collections::BTreeMap<_, _>>()))
+        )
+    }
+}
+
+/// Serializes any [`Htmlify`] as a generic element with a tag, attributes and 
+/// children
+pub(crate) struct HtmlNode<'a, T: Htmlify + ?Sized>(pub &'a T);
+
+impl<T: Htmlify + ?Sized> Serialize for HtmlNode<'_, T>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        use serde::ser::SerializeStruct;
+        let mut node = serializer.serialize_struct("HtmlNode", 3)?;
+        node.serialize_field("tag", &self.0.tag())?;
+        node.serialize_field("attributes", &AttributeMap(&self.0.attributes()))?;
+        node.serialize_field("children", &self.0.inner_html().iter().map(|child| HtmlNode(&**child)).collect::<Vec<_>>())?;
+        node.end()
     }
 }
 
 /// Helper function to attach JsFunctions to a serialized JsValue
+#[cfg(feature = "web")]
 pub(crate) fn define_property(src: &Object, name: &str, value: &Object)
 {
     if src.unchecked_ref::<JsValue>() != &JsValue::UNDEFINED
@@ -107,6 +405,120 @@ pub(crate) fn define_property(src: &Object, name: &str, value: &Object)
     }
 }
 
+/// Serializes a registration definition into a plain javascript object. Maps 
+/// (such as schemas) are serialized as objects rather than ES2015 `Map`s, 
+/// since that's what Aframe expects.
+#[cfg(feature = "web")]
+pub(crate) fn to_js_object<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error>
+{
+    value.serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true))
+}
+
+/// An error which occurred while registering something in Aframe.
+#[cfg(feature = "web")]
+#[derive(Debug)]
+pub enum RegError
+{
+    /// The definition could not be converted into a javascript object
+    Serialization(serde_wasm_bindgen::Error),
+    /// Aframe threw an exception during registration (for example, because 
+    /// the name was already registered)
+    Js(JsValue)
+}
+
+#[cfg(feature = "web")]
+impl Display for RegError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result 
+    {
+        match self
+        {
+            Self::Serialization(err) => write!(f, "Failed to serialize definition: {}", err),
+            Self::Js(err) => write!(f, "Aframe registration failed: {:?}", err)
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+impl std::error::Error for RegError {}
+
+#[cfg(feature = "web")]
+impl From<serde_wasm_bindgen::Error> for RegError
+{
+    fn from(err: serde_wasm_bindgen::Error) -> Self 
+    {
+        Self::Serialization(err)
+    }
+}
+
+#[cfg(feature = "web")]
+impl From<JsValue> for RegError
+{
+    fn from(err: JsValue) -> Self 
+    {
+        Self::Js(err)
+    }
+}
+
+/// Helper function to register a list of named definitions in order, collecting
+/// every failure along with the name of the registration that failed.
+#[cfg(feature = "web")]
+pub(crate) fn register_each<T>
+(
+    definitions: &[(&str, T)], 
+    mut register: impl FnMut(&str, &T) -> Result<(), RegError>
+) -> Result<(), Vec<(String, RegError)>>
+{
+    let errors: Vec<(String, RegError)> = definitions.iter()
+        .filter_map(|(name, definition)| register(name, definition).err().map(|err| (name.to_string(), err)))
+        .collect();
+    if errors.is_empty()
+    {
+        Ok(())
+    }
+    else
+    {
+        Err(errors)
+    }
+}
+
+/// Builds a schema `HashMap` declaratively, one `name: constructor(default)` 
+/// entry per property, where `constructor` is any `AframeProperty` constructor
+/// and the default is optional. Names may be string literals or identifiers.
+/// This is the same syntax accepted inline by `component_def!` and 
+/// `system_def!`, and is useful for building a schema ahead of time. Build the
+/// `HashMap` directly for schemas which are only known at runtime.
+/// ```ignore
+/// let schema = schema!
+/// {
+///     updateFreq: number(500.0),
+///     "textColor": color_rgb(color::LIGHTBLUE),
+///     label: string()
+/// };
+/// ```
+#[macro_export]
+macro_rules! schema
+{
+    ($($key:tt: $ctor:ident($($default:expr)?)),* $(,)?) => 
+    {
+        {
+            let mut schema = std::collections::HashMap::new();
+            $(
+                schema.insert
+                (
+                    $crate::schema!(@key $key), 
+                    $crate::utils::AframeProperty::$ctor($crate::schema!(@default $($default)?))
+                );
+            )*
+            schema
+        }
+    };
+    (@key $key:ident) => { stringify!($key) };
+    (@key $key:literal) => { $key };
+    (@default) => { None };
+    (@default $default:expr) => { Some($default.into()) };
+}
+
 /// A property used for some registrations in Aframe. 
 /// Contains the type string and the default value.
 #[derive(Serialize, Clone)]
@@ -145,6 +557,11 @@ impl AframeProperty
         Self { component_type: "color", default: default.map(AframeVal::Str) }
     }
 
+    pub fn color_rgb(default: Option<color::Rgb>) -> Self
+    {
+        Self { component_type: "color", default: default.map(AframeVal::from) }
+    }
+
     pub fn int(default: Option<i64>) -> Self
     {
         Self { component_type: "int", default: default.map(AframeVal::Int) }
@@ -209,6 +626,14 @@ pub enum AframeVal
     Vec4(Vector4)
 }
 
+impl From<color::Rgb> for AframeVal
+{
+    fn from(color: color::Rgb) -> Self 
+    {
+        Self::Str(color.into())
+    }
+}
+
 impl Serialize for AframeVal
 {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
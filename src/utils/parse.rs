@@ -0,0 +1,457 @@
+//! A lightweight HTML parser for loading hand-authored Aframe scenes into the
+//! typed model. Only the subset of HTML needed to describe a scene is
+//! supported: elements, quoted, unquoted and bare attributes, comments and
+//! doctype declarations. Text content is ignored.
+
+use std::borrow::Cow;
+use crate::
+{
+    Asset, AssetItem, Assets, Attribute, Audio, ComponentVec, Entity, Image, Mixin, Preload, ResponseType, Scene, Video, VideoOptions,
+    component::{self, Component, Position, RawComponent, Rotation, Scale, Visible}
+};
+
+/// An error encountered while parsing HTML, along with the byte offset into the
+/// input at which it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError
+{
+    pub offset: usize,
+    pub message: String
+}
+impl std::fmt::Display for ParseError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+impl std::error::Error for ParseError {}
+
+/// Elements which never have children or a closing tag
+const VOID_ELEMENTS: &[&str] = &["img", "source", "br", "hr", "meta", "link", "input"];
+
+/// Elements whose content is raw text, read verbatim up to their closing tag
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Attributes which are plain HTML attributes rather than components when
+/// found on an `<a-scene>` or `<a-entity>`
+const HTML_ATTRIBUTES: &[&str] = &["id", "class", "style", "title", "hidden", "slot", "lang", "dir", "tabindex", "mixin"];
+
+/// Names of Aframe's built-in components. On a primitive, attributes with these
+/// names are components and anything else is assumed to be a primitive mapping.
+const KNOWN_COMPONENTS: &[&str] = &
+[
+    component::Animation::NAME, component::Background::NAME, component::Camera::NAME, component::Cursor::NAME,
+    component::DaydreamControls::NAME, component::DeviceOrientationPermissionUI::NAME, component::Embedded::NAME,
+    component::Fog::NAME, component::GearVRControls::NAME, component::Geometry::NAME, component::GltfModel::NAME,
+    component::HandControls::NAME, component::HandTrackingControls::NAME, component::LaserControls::NAME,
+    component::Inspector::NAME, component::KeyboardShortcuts::NAME, component::Layer::NAME, component::Light::NAME,
+    component::Line::NAME, component::Link::NAME, component::LoadingScreen::NAME, component::LookControls::NAME,
+    component::MagicLeapControls::NAME, component::Material::NAME, component::ObbCollider::NAME,
+    component::ObjModel::NAME, component::OculusGoControls::NAME, component::OculusTouchControls::NAME,
+    component::Pool::NAME, Position::NAME, component::Renderer::NAME, component::RayCaster::NAME, Rotation::NAME,
+    Scale::NAME, component::Screenshot::NAME, component::Shadow::NAME, component::Sound::NAME,
+    component::Stats::NAME, component::Text::NAME, component::TrackedControls::NAME, Visible::NAME,
+    component::ViveControls::NAME, component::ViveFocusControls::NAME, component::VrModeUi::NAME,
+    component::WasdControls::NAME, component::WebXR::NAME, component::WindowsMotionControls::NAME
+];
+
+/// An element of the parsed document
+struct Node
+{
+    offset: usize,
+    tag: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>
+}
+
+struct Parser<'a>
+{
+    html: &'a str,
+    pos: usize
+}
+
+impl<'a> Parser<'a>
+{
+    fn error(&self, offset: usize, message: impl Into<String>) -> ParseError
+    {
+        ParseError { offset, message: message.into() }
+    }
+
+    fn rest(&self) -> &'a str
+    {
+        &self.html[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self)
+    {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Moves past the next occurrence of `pattern`
+    fn skip_past(&mut self, pattern: &str) -> Result<(), ParseError>
+    {
+        match self.rest().find(pattern)
+        {
+            Some(i) =>
+            {
+                self.pos += i + pattern.len();
+                Ok(())
+            },
+            None => Err(self.error(self.pos, format!("Expected {}", pattern)))
+        }
+    }
+
+    /// Reads a tag or attribute name
+    fn name(&mut self) -> &'a str
+    {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/' | '<' | '"' | '\''))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Reads a quoted or unquoted attribute value
+    fn value(&mut self) -> Result<String, ParseError>
+    {
+        let rest = self.rest();
+        match rest.chars().next()
+        {
+            Some(quote @ ('"' | '\'')) =>
+            {
+                let len = rest[1..].find(quote)
+                    .ok_or_else(|| self.error(self.pos, "Unterminated attribute value"))?;
+                self.pos += len + 2;
+                Ok(decode(&rest[1..len + 1]))
+            },
+            _ =>
+            {
+                let len = rest.find(|c: char| c.is_whitespace() || c == '>').unwrap_or(rest.len());
+                self.pos += len;
+                Ok(decode(&rest[..len]))
+            }
+        }
+    }
+
+    /// Parses sibling nodes until the closing tag of `parent`, or until the end
+    /// of the input if there is no parent.
+    fn nodes(&mut self, parent: Option<(&str, usize)>) -> Result<Vec<Node>, ParseError>
+    {
+        let mut nodes = vec!();
+        while let Some(i) = self.rest().find('<')
+        {
+            self.pos += i;
+            let rest = self.rest();
+            if rest.starts_with("<!--")
+            {
+                self.skip_past("-->")?;
+            }
+            else if rest.starts_with("<!") || rest.starts_with("<?")
+            {
+                self.skip_past(">")?;
+            }
+            else if rest.starts_with("</")
+            {
+                let start = self.pos;
+                self.pos += 2;
+                let name = self.name().to_ascii_lowercase();
+                self.skip_past(">")?;
+                match parent
+                {
+                    Some((tag, _)) if tag == name => return Ok(nodes),
+                    _ if VOID_ELEMENTS.contains(&name.as_str()) => (),
+                    _ => return Err(self.error(start, format!("Unexpected closing tag </{}>", name)))
+                }
+            }
+            else
+            {
+                nodes.push(self.element()?);
+            }
+        }
+        match parent
+        {
+            Some((tag, offset)) => Err(self.error(offset, format!("Unclosed <{}>", tag))),
+            None => Ok(nodes)
+        }
+    }
+
+    /// Moves past the content and closing tag of a raw text element, which may
+    /// contain `<` without starting a tag.
+    fn skip_raw_text(&mut self, tag: &str, offset: usize) -> Result<(), ParseError>
+    {
+        let closing = format!("</{}", tag);
+        let end = self.rest().to_ascii_lowercase().find(&closing)
+            .ok_or_else(|| self.error(offset, format!("Unclosed <{}>", tag)))?;
+        self.pos += end + closing.len();
+        self.skip_past(">")
+    }
+
+    /// Parses an element, including its children and closing tag
+    fn element(&mut self) -> Result<Node, ParseError>
+    {
+        let offset = self.pos;
+        self.pos += 1;
+        let tag = self.name().to_ascii_lowercase();
+        if tag.is_empty()
+        {
+            return Err(self.error(offset, "Expected a tag name"));
+        }
+        let mut attributes = vec!();
+        loop
+        {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>")
+            {
+                self.pos += 2;
+                return Ok(Node { offset, tag, attributes, children: vec!() });
+            }
+            if rest.starts_with('>')
+            {
+                self.pos += 1;
+                break;
+            }
+            let attr_offset = self.pos;
+            let name = self.name();
+            if name.is_empty()
+            {
+                return Err(self.error(attr_offset, format!("Unterminated <{}>", tag)));
+            }
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=')
+            {
+                self.pos += 1;
+                self.skip_whitespace();
+                self.value()?
+            }
+            else
+            {
+                String::new()
+            };
+            attributes.push((name.to_owned(), value));
+        }
+        let children = if VOID_ELEMENTS.contains(&tag.as_str())
+        {
+            vec!()
+        }
+        else if RAW_TEXT_ELEMENTS.contains(&tag.as_str())
+        {
+            self.skip_raw_text(&tag, offset)?;
+            vec!()
+        }
+        else
+        {
+            self.nodes(Some((&tag, offset)))?
+        };
+        Ok(Node { offset, tag, attributes, children })
+    }
+}
+
+/// Decodes the character references which may appear in attribute values
+fn decode(value: &str) -> String
+{
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn find_scene(nodes: &[Node]) -> Option<&Node>
+{
+    nodes.iter().find_map(|node| if node.tag == "a-scene" { Some(node) } else { find_scene(&node.children) })
+}
+
+fn get<'a>(node: &'a Node, name: &str) -> Option<&'a str>
+{
+    node.attributes.iter().find(|(attr, _)| attr == name).map(|(_, value)| value.as_str())
+}
+
+/// Parses a component from its attribute. Components without a parser are
+/// kept as a [`RawComponent`].
+fn parse_component(name: &str, value: &str) -> Box<dyn Component>
+{
+    let vec3 = || -> Option<[f32; 3]>
+    {
+        let mut coords = value.split_whitespace().map(|coord| coord.parse::<f32>().ok());
+        let parsed = [coords.next()??, coords.next()??, coords.next()??];
+        coords.next().is_none().then_some(parsed)
+    };
+    let parsed: Option<Box<dyn Component>> = match name
+    {
+        "position" => vec3().map(|[x, y, z]| Box::new(Position { x, y, z }) as Box<dyn Component>),
+        "rotation" => vec3().map(|[x, y, z]| Box::new(Rotation { x, y, z }) as Box<dyn Component>),
+        "scale" => vec3().map(|[x, y, z]| Box::new(Scale { x, y, z }) as Box<dyn Component>),
+        "visible" => value.trim().parse().ok().map(|visible| Box::new(Visible { visible }) as Box<dyn Component>),
+        _ => None
+    };
+    parsed.unwrap_or_else(|| Box::new(RawComponent(Cow::Owned(value.to_owned()))))
+}
+
+/// Splits the attributes of an element into plain attributes and components
+fn split_attributes(node: &Node, primitive: bool) -> (Vec<Attribute>, ComponentVec)
+{
+    let mut attributes = vec!();
+    let mut components = ComponentVec::default();
+    for (name, value) in &node.attributes
+    {
+        let is_component = if primitive
+        {
+            KNOWN_COMPONENTS.contains(&name.split("__").next().unwrap_or(name))
+        }
+        else
+        {
+            !HTML_ATTRIBUTES.contains(&name.as_str()) && !name.starts_with("data-") && !name.starts_with("aria-")
+        };
+        if is_component
+        {
+            components.push((Cow::Owned(name.clone()), parse_component(name, value)));
+        }
+        else
+        {
+            attributes.push(Attribute::new(name.clone(), value.clone()));
+        }
+    }
+    (attributes, components)
+}
+
+/// Removes the attributes named after a built-in component from `attributes`,
+/// returning them parsed as components. Other attributes are left in place.
+pub(crate) fn promote_attributes(attributes: &mut Vec<Attribute>) -> Vec<(Cow<'static, str>, Box<dyn Component>)>
+{
+    let mut components = vec!();
+    attributes.retain(|attr|
+    {
+        let is_component = KNOWN_COMPONENTS.contains(&attr.name.split("__").next().unwrap_or(&attr.name));
+        if is_component
+        {
+            components.push((attr.name.clone(), parse_component(&attr.name, &attr.value)));
+        }
+        !is_component
+    });
+    components
+}
+
+fn parse_entity(node: &Node) -> Result<Entity, ParseError>
+{
+    let children = node.children.iter().map(parse_entity).collect::<Result<_, _>>()?;
+    if node.tag == "a-entity"
+    {
+        let (attributes, components) = split_attributes(node, false);
+        Ok(Entity::new(attributes, components.0, children))
+    }
+    else if node.tag.contains('-')
+    {
+        let (attributes, components) = split_attributes(node, true);
+        Ok(Entity::new_primitive(Cow::Owned(node.tag.clone()), attributes, components.0, children))
+    }
+    else
+    {
+        Err(ParseError { offset: node.offset, message: format!("Expected an entity or primitive, found <{}>", node.tag) })
+    }
+}
+
+fn parse_preload(node: &Node) -> Preload
+{
+    match get(node, "preload")
+    {
+        Some("auto") | Some("") => Preload::Auto,
+        Some("metadata") => Preload::Metadata,
+        _ => Preload::None
+    }
+}
+
+fn parse_asset(node: &Node) -> Result<Asset, ParseError>
+{
+    let id = Cow::Owned(get(node, "id").unwrap_or_default().to_owned());
+    let src = Cow::Owned(get(node, "src").unwrap_or_default().to_owned());
+    let autoplay = get(node, "autoplay").is_some_and(|autoplay| autoplay != "false");
+    match node.tag.as_str()
+    {
+        "img" => Ok(Image::new(id, src)),
+        "video" =>
+        {
+            let flag = |name| get(node, name).is_some_and(|value| value != "false");
+            let options = VideoOptions 
+            { 
+                autoplay, 
+                preload: parse_preload(node), 
+                muted: flag("muted"), 
+                looping: flag("loop"), 
+                plays_inline: flag("playsinline") 
+            };
+            Ok(Video::with_options(id, src, options))
+        },
+        "audio" => Ok(Asset::Audio(Audio { id, src, autoplay, preload: parse_preload(node) })),
+        "a-asset-item" =>
+        {
+            let response_type = match get(node, "response-type")
+            {
+                Some("arraybuffer") => Some(ResponseType::ArrayBuffer),
+                Some("text") => Some(ResponseType::Text),
+                _ => None
+            };
+            Ok(Asset::Item(AssetItem { id, src, response_type }))
+        },
+        "a-mixin" =>
+        {
+            let components = node.attributes.iter()
+                .filter(|(name, _)| name != "id")
+                .map(|(name, value)| (Cow::Owned(name.clone()), parse_component(name, value)))
+                .collect();
+            Ok(Mixin::new(id, components))
+        },
+        tag => Err(ParseError { offset: node.offset, message: format!("Unsupported asset <{}>", tag) })
+    }
+}
+
+fn parse_assets(node: &Node) -> Result<Assets, ParseError>
+{
+    let mut timeout_ms = 0;
+    let mut attributes = vec!();
+    for (name, value) in &node.attributes
+    {
+        if name == "timeout"
+        {
+            timeout_ms = value.trim().parse().map_err(|_| ParseError
+            {
+                offset: node.offset,
+                message: format!("Invalid assets timeout: {}", value)
+            })?;
+        }
+        else
+        {
+            attributes.push(Attribute::new(name.clone(), value.clone()));
+        }
+    }
+    let assets = node.children.iter().map(parse_asset).collect::<Result<_, _>>()?;
+    Ok(Assets::new(timeout_ms, assets).with_attributes(attributes))
+}
+
+/// Parses the first `<a-scene>` in the given HTML
+pub(crate) fn parse_scene(html: &str) -> Result<Scene, ParseError>
+{
+    let nodes = Parser { html, pos: 0 }.nodes(None)?;
+    let node = find_scene(&nodes)
+        .ok_or_else(|| ParseError { offset: 0, message: "No <a-scene> element found".to_owned() })?;
+    let (attributes, components) = split_attributes(node, false);
+    let mut assets = Assets::default();
+    let mut children = vec!();
+    for child in &node.children
+    {
+        if child.tag == "a-assets"
+        {
+            assets = parse_assets(child)?;
+        }
+        else
+        {
+            children.push(parse_entity(child)?);
+        }
+    }
+    Ok(Scene::new(attributes, assets, components.0, children))
+}